@@ -16,6 +16,9 @@ pub enum DaoError {
     //投票持续时间必须有效
     #[msg("Vote duration must be a positive value.")]
     InvalidVoteDuration,
+    //通过比例必须在 1-100 之间
+    #[msg("Pass threshold percentage must be between 1 and 100.")]
+    InvalidPassThreshold,
     //支付金额必须有效
     #[msg("Recurring payment amount must be greater than 0.")]
     InvalidPaymentAmount,
@@ -40,6 +43,10 @@ pub enum DaoError {
     ProposalAlreadyExecuted,
     #[msg("Signer has already approved this proposal.")]
     AlreadyApproved,
+    #[msg("Signer has already rejected this proposal.")]
+    AlreadyRejected,
+    #[msg("Signer has already voted to cancel this proposal as spam.")]
+    AlreadyVotedSpam,
     #[msg("Signer is already part of the DAO.")]
     SignerAlreadyExists,
     #[msg("Signer to be removed was not found.")]
@@ -55,6 +62,23 @@ pub enum DaoError {
 
     #[msg("No tokens staked to unstake.")]
     NoTokensStaked,
+
+    #[msg("Stake is locked until the proposals you voted on have ended.")]
+    StakeLocked,
+    #[msg("Lock duration must be positive and no longer than the maximum lock duration.")]
+    InvalidLockDuration,
+    #[msg("The new lock must end later than the current one.")]
+    LockNotExtended,
+    #[msg("Unbonding period cannot be negative.")]
+    InvalidUnbondingPeriod,
+    #[msg("No unbonded tokens to withdraw.")]
+    NothingToWithdraw,
+    #[msg("Unbonded tokens are not yet withdrawable.")]
+    UnbondingNotComplete,
+    #[msg("The governance mint has an extension that is not supported.")]
+    UnsupportedMintExtension,
+    #[msg("Stake history at the proposal snapshot has been pruned by later stake changes.")]
+    StakeHistoryTruncated,
     // --- 质押投票相关错误 ---
     #[msg("You must have a stake in the DAO to create a proposal.")]
     NotStaked,
@@ -64,6 +88,10 @@ pub enum DaoError {
     VotePeriodNotOver,
     #[msg("You have already voted on this proposal.")]
     AlreadyVoted,
+    #[msg("This vote was cast by your delegate and cannot be changed.")]
+    VoteCastByDelegate,
+    #[msg("A vote that carries delegated power cannot be relinquished; change it instead.")]
+    CannotRelinquishDelegatedVote,
     #[msg("The proposal did not meet the required quorum.")]
     QuorumNotReached,
     #[msg("The proposal failed because there were more 'No' votes than 'Yes' votes.")]
@@ -80,4 +108,118 @@ pub enum DaoError {
     
     #[msg("Quorum requirement not met for this proposal.")]
     QuorumNotMet,
+
+    // --- 提案状态机 ---
+    #[msg("The proposal is not in the required state for this action.")]
+    InvalidProposalState,
+
+    // --- 投票权委托 ---
+    #[msg("You cannot delegate voting power to yourself.")]
+    CannotDelegateToSelf,
+    #[msg("Delegation does not belong to this delegate or DAO.")]
+    InvalidDelegation,
+    #[msg("Delegated votes must be passed as (delegation, stake account, vote record) triples.")]
+    InvalidDelegationAccounts,
+    #[msg("All vote records cast on behalf of delegators must be passed.")]
+    MissingDelegatorVoteRecords,
+
+    // --- 多操作提案 ---
+    #[msg("A proposal must contain between 1 and 5 actions.")]
+    InvalidActionCount,
+    #[msg("Not enough remaining accounts were provided for the proposal actions.")]
+    MissingActionAccounts,
+    #[msg("The account provided for a proposal action does not match the expected address.")]
+    InvalidActionAccount,
+
+    // --- 任意指令提案 ---
+    #[msg("Instruction count must be greater than 0 and match the proposal.")]
+    InvalidInstructionCount,
+    #[msg("Proposal instructions can only be attached while the proposal is a draft.")]
+    ProposalTransactionLocked,
+    #[msg("Every instruction action must have its proposal transaction attached before submission.")]
+    MissingProposalTransaction,
+    #[msg("This proposal type does not carry instructions.")]
+    NotAnInstructionProposal,
+    #[msg("The proposal transaction account does not belong to this proposal.")]
+    InvalidProposalTransaction,
+    #[msg("Proposal instructions cannot invoke the DAO program itself.")]
+    InvalidProposalInstruction,
+    #[msg("Only the treasury may sign proposal instructions.")]
+    InvalidInstructionSigner,
+    #[msg("A payer is required to create accounts for this action.")]
+    MissingPayer,
+
+    // --- 质押奖励 ---
+    #[msg("Reward amount must be greater than 0.")]
+    InvalidRewardAmount,
+    #[msg("No rewards to claim.")]
+    NoRewardsToClaim,
+
+    // --- 执行时间锁 ---
+    #[msg("Hold-up time cannot be negative.")]
+    InvalidHoldUpTime,
+    #[msg("Execution window must be a positive value.")]
+    InvalidExecutionWindow,
+    #[msg("The proposal is still in its timelock and cannot be executed yet.")]
+    ProposalTimelocked,
+    #[msg("The execution window for this proposal has passed.")]
+    ProposalExpired,
+
+    // --- 守护者否决 ---
+    #[msg("Signer is not the DAO guardian.")]
+    UnauthorizedGuardian,
+    #[msg("The DAO has no guardian.")]
+    GuardianNotSet,
+    #[msg("Guardian cannot be the default public key.")]
+    InvalidGuardian,
+    #[msg("Veto reason is too long.")]
+    VetoReasonTooLong,
+
+    // --- 质押者提案押金 ---
+    #[msg("The proposal has no deposit left.")]
+    NoProposalDeposit,
+    #[msg("The deposit can only be refunded after the proposal has entered voting.")]
+    DepositNotRefundable,
+
+    // --- 法定人数 ---
+    #[msg("Quorum value is invalid for the selected quorum mode.")]
+    InvalidQuorum,
+
+    // --- 租金回收 ---
+    #[msg("Proposal retention period cannot be negative.")]
+    InvalidRetentionPeriod,
+    #[msg("The proposal has not been finalized yet.")]
+    ProposalNotFinalized,
+    #[msg("The proposal retention period has not passed yet.")]
+    RetentionPeriodNotOver,
+    #[msg("Rent receiver does not match the account that paid for the vote record.")]
+    InvalidRentReceiver,
+
+    // --- 提案元数据 ---
+    #[msg("Proposal title is too long.")]
+    TitleTooLong,
+    #[msg("Proposal description is too long.")]
+    DescriptionTooLong,
+    #[msg("Proposal metadata URI is too long.")]
+    MetadataUriTooLong,
+    #[msg("Proposal metadata can only be edited before the first approval.")]
+    ProposalMetadataLocked,
+
+    // --- 多选提案 ---
+    #[msg("Invalid proposal options.")]
+    InvalidProposalOptions,
+    #[msg("This vote choice is not valid for the proposal.")]
+    InvalidVoteChoice,
+
+    // --- 理事会代币 ---
+    #[msg("The DAO has no council mint.")]
+    CouncilNotConfigured,
+    #[msg("The mint does not match the governing token.")]
+    InvalidGoverningMint,
+    #[msg("Invalid voter population for this proposal.")]
+    InvalidVoterPopulation,
+
+    // --- 治理规则 ---
+    #[msg("No governance rule is configured for this proposal kind.")]
+    GovernanceRuleNotFound,
 }
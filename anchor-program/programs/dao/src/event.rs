@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::{instructions::VoteChoice, state::ProposalType};
+use crate::state::{
+    DaoParameters, GoverningToken, ProposalOption, ProposalState, ProposalType, QuorumMode, VoteChoice, VoteTally,
+    VoterPopulation, VotingMode,
+};
 ///dao初始化
 #[event]
 pub struct DaoInitialized {
@@ -9,10 +12,20 @@ pub struct DaoInitialized {
     pub token_mint: Pubkey,
     pub threshold: u8,
     pub vote_duration: i64,
-    pub quorum: u32,
+    pub quorum_mode: QuorumMode,
+    pub quorum: u64,
     pub staking_yield_rate: u16,
     pub pass_threshold_percentage: u8,
     pub min_staking_amount: u64,
+    pub voting_mode: VotingMode,
+    pub hold_up_time: i64,
+    pub execution_window: i64,
+    pub proposal_deposit: u64,
+    pub proposal_retention_period: i64,
+    pub unbonding_period: i64,
+    pub council_mint: Option<Pubkey>,
+    pub council_voting_mode: VotingMode,
+    pub council_quorum: u64,
 }
 
 #[event]
@@ -25,9 +38,93 @@ pub struct ProposalCreated {
     pub proposal_id: u64,
     /// 提案发起人
     pub proposer: Pubkey,
-    /// 提案的具体类型和内容
-    pub proposal_type: ProposalType,
+    /// 提案包含的操作
+    pub actions: Vec<ProposalType>,
+    /// 多选提案的选项
+    pub options: Vec<ProposalOption>,
+    /// 参与投票的人群
+    pub voter_population: VoterPopulation,
+    /// 提案元数据账户
+    pub proposal_metadata: Pubkey,
+    /// 提案标题
+    pub title: String,
+    /// 链下提案文档的地址
+    pub metadata_uri: String,
+    /// 链下提案文档的内容哈希
+    pub content_hash: [u8; 32],
+}
+
+/// 发起人提交草稿、提案进入多签审批时触发
+#[event]
+pub struct ProposalSubmitted {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提案账户
+    pub proposal: Pubkey,
+    /// 提案的唯一ID
+    pub proposal_id: u64,
+    /// 提案发起人
+    pub proposer: Pubkey,
 }
+
+/// 发起人在第一次批准前修改提案元数据时触发
+#[event]
+pub struct ProposalMetadataUpdated {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提案账户
+    pub proposal: Pubkey,
+    /// 提案元数据账户
+    pub proposal_metadata: Pubkey,
+    /// 新的提案标题
+    pub title: String,
+    /// 新的提案描述
+    pub description: String,
+    /// 新的链下提案文档地址
+    pub metadata_uri: String,
+    /// 新的链下提案文档内容哈希
+    pub content_hash: [u8; 32],
+}
+
+/// 治理提案修改 DAO 参数时触发，记录修改前后的值
+#[event]
+pub struct DaoParametersUpdated {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 发起修改的提案账户
+    pub proposal: Pubkey,
+    /// 修改前的参数
+    pub old_parameters: DaoParameters,
+    /// 修改后的参数
+    pub new_parameters: DaoParameters,
+}
+
+/// 提案的待执行指令被写入时触发
+#[event]
+pub struct ProposalInstructionsAdded {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 对应的操作序号
+    pub action_index: u8,
+    /// 存放指令的账户
+    pub proposal_transaction: Pubkey,
+    /// 指令数量
+    pub instruction_count: u8,
+}
+
+/// 提案的待执行指令账户被关闭时触发
+#[event]
+pub struct ProposalTransactionClosed {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 被关闭的指令账户
+    pub proposal_transaction: Pubkey,
+    /// 接收返还租金的提案发起人
+    pub proposer: Pubkey,
+}
+
 #[event]
 pub struct ProposalApproved {
     /// 关联的 DAO 账户
@@ -44,6 +141,125 @@ pub struct ProposalApproved {
     pub threshold: u8,
 }
 
+/// 多签签名者否决提案时触发
+#[event]
+pub struct ProposalRejected {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 被否决的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 本次操作的否决者
+    pub rejector: Pubkey,
+    /// 当前的否决总数
+    pub current_rejections: u64,
+    /// 要求的阈值
+    pub threshold: u8,
+    /// 否决后提案的状态（PendingApproval 或 Rejected）
+    pub state: ProposalState,
+}
+
+/// 多签签名者投票将质押者提案作为垃圾提案取消时触发
+#[event]
+pub struct SpamCancellationVoted {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 本次投票的签名者
+    pub signer: Pubkey,
+    /// 当前的垃圾提案投票数
+    pub current_spam_votes: u64,
+    /// 要求的阈值
+    pub threshold: u8,
+}
+
+/// 发起人在投票开始前取消提案时触发
+#[event]
+pub struct ProposalCancelled {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 被取消的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 提案发起人
+    pub proposer: Pubkey,
+    /// 提案包含的操作
+    pub actions: Vec<ProposalType>,
+}
+
+/// 已结束的提案账户被关闭时触发，保留提案的最终结果供索引器使用
+#[event]
+pub struct ProposalClosed {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 被关闭的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 提案发起人，接收返还的租金
+    pub proposer: Pubkey,
+    /// 提案的标题
+    pub title: String,
+    /// 提案包含的操作
+    pub actions: Vec<ProposalType>,
+    /// 提案的最终状态
+    pub state: ProposalState,
+    /// 最终赞成票数
+    pub yes_votes: u64,
+    /// 最终反对票数
+    pub no_votes: u64,
+    /// 最终弃权票数
+    pub abstain_votes: u64,
+    /// 多选提案各选项的最终得票
+    pub options: Vec<ProposalOption>,
+    /// 两者共同投票时理事会的最终计票
+    pub council_votes: VoteTally,
+    /// 最终投票人数
+    pub voter_count: u32,
+    /// 已执行的操作数量
+    pub executed_actions: u8,
+    /// 提案进入最终状态的时间
+    pub finalized_at: i64,
+}
+
+/// 投票记录在提案结束后被关闭时触发
+#[event]
+pub struct VoteRecordClosed {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 投票者
+    pub voter: Pubkey,
+    /// 投票选项
+    pub choice: VoteChoice,
+    /// 投票权重
+    pub weight: u64,
+    /// 代为投票的受托人
+    pub delegate: Option<Pubkey>,
+    /// 接收返还租金的账户
+    pub rent_receiver: Pubkey,
+}
+
+/// 提案中的单个操作被执行时触发
+#[event]
+pub struct ProposalActionExecuted {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 所属的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 操作在提案中的序号
+    pub action_index: u8,
+    /// 被执行的操作
+    pub action: ProposalType,
+}
+
+/// 提案的全部操作执行完毕时触发
 #[event]
 pub struct ProposalExecuted {
     /// 关联的 DAO 账户
@@ -52,9 +268,55 @@ pub struct ProposalExecuted {
     pub proposal: Pubkey,
     /// 提案的ID
     pub proposal_id: u64,
-    /// 被执行的具体提案类型和内容
-    pub proposal_type: ProposalType,
+    /// 被执行的全部操作（多选提案仅包含胜出选项的操作）
+    pub actions: Vec<ProposalType>,
+    /// 最早可执行时间
+    pub executable_at: i64,
+    /// 最晚可执行时间
+    pub execution_deadline: i64,
 }
+/// 投票期结束后提案被计票时触发
+#[event]
+pub struct ProposalFinalized {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 被计票的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 计票后的状态 (Succeeded/Defeated/Expired)
+    pub state: ProposalState,
+    /// 赞成票总数
+    pub yes_votes: u64,
+    /// 反对票总数
+    pub no_votes: u64,
+    /// 弃权票总数
+    pub abstain_votes: u64,
+    /// 多选提案各选项的得票
+    pub options: Vec<ProposalOption>,
+    /// 多选提案的胜出选项
+    pub winning_option: Option<u8>,
+    /// 两者共同投票时理事会的计票
+    pub council_votes: VoteTally,
+    /// 参与投票的总人数
+    pub voter_count: u32,
+}
+
+/// 守护者否决提案时触发
+#[event]
+pub struct ProposalVetoed {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 被否决的提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 执行否决的守护者
+    pub guardian: Pubkey,
+    /// 否决理由
+    pub reason: String,
+}
+
 ///领取定期支付事件
 #[event]
 pub struct PaymentClaimed {
@@ -78,10 +340,33 @@ pub struct TokensStaked {
     pub stake_account: Pubkey,
     /// 质押者
     pub staker: Pubkey,
-    /// 本次质押的数量
+    /// 质押的治理代币种类
+    pub governing_token: GoverningToken,
+    /// 本次质押的数量（扣除转账手续费后）
     pub amount_staked: u64,
     /// 该用户质押后的总数量
     pub new_total_for_staker: u64,
+    /// 锁仓到期时间
+    pub lock_end: i64,
+    /// 当前锁仓倍数（基点）
+    pub lock_multiplier: u16,
+}
+
+/// 质押者延长锁仓时触发
+#[event]
+pub struct LockExtended {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 质押信息账户
+    pub stake_account: Pubkey,
+    /// 质押者
+    pub staker: Pubkey,
+    /// 原锁仓到期时间
+    pub old_lock_end: i64,
+    /// 新的锁仓到期时间
+    pub new_lock_end: i64,
+    /// 新的锁仓倍数（基点）
+    pub lock_multiplier: u16,
 }
 
 ///  用户成功赎回代币事件
@@ -89,12 +374,59 @@ pub struct TokensStaked {
 pub struct TokensUnstaked {
     /// 关联的 DAO 账户
     pub dao_state: Pubkey,
-    /// (已关闭的)质押信息账户
+    /// 质押信息账户（余额归零时已关闭）
     pub stake_account: Pubkey,
     /// 赎回者
     pub staker: Pubkey,
+    /// 赎回的治理代币种类
+    pub governing_token: GoverningToken,
     /// 赎回的代币数量
     pub amount_unstaked: u64,
+    /// 赎回后剩余的质押数量
+    pub remaining_amount: u64,
+    /// 解绑中的代币总数
+    pub unbonding_amount: u64,
+    /// 解绑中的代币可提取的时间
+    pub release_at: i64,
+}
+
+/// 向奖励金库注入奖励时触发
+#[event]
+pub struct RewardsFunded {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 注资者
+    pub funder: Pubkey,
+    /// 注入的奖励数量
+    pub amount: u64,
+    /// 注入后尚未分配的奖励总数
+    pub undistributed_rewards: u64,
+}
+
+/// 质押者领取奖励时触发
+#[event]
+pub struct RewardsClaimed {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 质押信息账户
+    pub stake_account: Pubkey,
+    /// 领取者
+    pub staker: Pubkey,
+    /// 领取的奖励数量
+    pub amount: u64,
+}
+
+/// 解绑完成的代币被提取时触发
+#[event]
+pub struct UnbondedTokensWithdrawn {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提取者
+    pub staker: Pubkey,
+    /// 提取的治理代币种类
+    pub governing_token: GoverningToken,
+    /// 提取的代币数量
+    pub amount: u64,
 }
 // --- 新增：质押投票相关事件 ---
 
@@ -113,10 +445,50 @@ pub struct StakeProposalCreated {
     pub title: String,
     /// 提案描述
     pub description: String,
-    /// 提案类型和内容
-    pub proposal_type: ProposalType,
-    /// 投票结束时间
-    pub end_time: i64,
+    /// 链下提案文档的地址
+    pub metadata_uri: String,
+    /// 链下提案文档的内容哈希
+    pub content_hash: [u8; 32],
+    /// 提案包含的操作
+    pub actions: Vec<ProposalType>,
+    /// 多选提案的选项
+    pub options: Vec<ProposalOption>,
+    /// 参与投票的人群
+    pub voter_population: VoterPopulation,
+    /// 托管的押金（lamports）
+    pub deposit_amount: u64,
+}
+
+/// 质押者提案押金退还给发起人时触发
+#[event]
+pub struct ProposalDepositRefunded {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 收到退款的发起人
+    pub proposer: Pubkey,
+    /// 退还的押金（lamports）
+    pub amount: u64,
+}
+
+/// 质押者提案被多签作为垃圾提案取消或被发起人自行取消、押金转入国库时触发
+#[event]
+pub struct ProposalDepositForfeited {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 提案账户
+    pub proposal: Pubkey,
+    /// 提案的ID
+    pub proposal_id: u64,
+    /// 提案发起人
+    pub proposer: Pubkey,
+    /// 使垃圾提案投票达到阈值的签名者；发起人自行取消时为发起人
+    pub cancelled_by: Pubkey,
+    /// 没收的押金（lamports）
+    pub amount: u64,
 }
 
 /// 当用户对质押提案进行投票时触发
@@ -126,18 +498,102 @@ pub struct VoteCasted {
     pub proposal: Pubkey,
     /// 投票者
     pub voter: Pubkey,
-    /// 投票选项 (Yes/No)
+    /// 投票选项 (Yes/No/Abstain)
+    pub choice: VoteChoice,
+    /// 本次投票实际计入的权重（按投票模式换算后）
+    pub weight: u64,
+    /// 换算前的质押数量（含委托）
+    pub raw_stake: u64,
+    /// 两者共同投票时计入理事会计票的权重
+    pub council_weight: u64,
+    /// 更新后的总赞成票数
+    pub new_yes_votes: u64,
+    /// 更新后的总反对票数
+    pub new_no_votes: u64,
+    /// 更新后的总弃权票数
+    pub new_abstain_votes: u64,
+    /// 更新后的总投票人数
+    pub new_voter_count: u32,
+    /// 本次投票中使用了其投票权的委托人
+    pub delegators: Vec<Pubkey>,
+    /// 更新后的理事会计票（两者共同投票时）
+    pub council_votes: VoteTally,
+}
+
+/// 投票者修改投票选项时触发
+#[event]
+pub struct VoteChanged {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 投票者
+    pub voter: Pubkey,
+    /// 原投票选项
+    pub old_choice: VoteChoice,
+    /// 新投票选项
+    pub new_choice: VoteChoice,
+    /// 投票权重
+    pub weight: u64,
+    /// 理事会计票中的投票权重
+    pub council_weight: u64,
+    /// 更新后的总赞成票数
+    pub new_yes_votes: u64,
+    /// 更新后的总反对票数
+    pub new_no_votes: u64,
+    /// 更新后的总弃权票数
+    pub new_abstain_votes: u64,
+    /// 随之修改投票选项的委托人
+    pub delegators: Vec<Pubkey>,
+}
+
+/// 投票者撤回投票时触发
+#[event]
+pub struct VoteRelinquished {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 投票者
+    pub voter: Pubkey,
+    /// 被撤回的投票选项
     pub choice: VoteChoice,
-    /// 本次投票的权重 (质押数量)
+    /// 被撤回的权重
     pub weight: u64,
+    /// 被撤回的理事会计票权重
+    pub council_weight: u64,
     /// 更新后的总赞成票数
     pub new_yes_votes: u64,
     /// 更新后的总反对票数
     pub new_no_votes: u64,
+    /// 更新后的总弃权票数
+    pub new_abstain_votes: u64,
     /// 更新后的总投票人数
     pub new_voter_count: u32,
 }
 
+/// 质押者将投票权委托给他人时触发
+#[event]
+pub struct VotingPowerDelegated {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// 委托记录账户
+    pub delegation: Pubkey,
+    /// 委托人
+    pub delegator: Pubkey,
+    /// 受托人
+    pub delegate: Pubkey,
+}
+
+/// 质押者收回委托时触发
+#[event]
+pub struct VotingPowerUndelegated {
+    /// 关联的 DAO 账户
+    pub dao_state: Pubkey,
+    /// (已关闭的)委托记录账户
+    pub delegation: Pubkey,
+    /// 委托人
+    pub delegator: Pubkey,
+    /// 受托人
+    pub delegate: Pubkey,
+}
+
 /// 当一个质押提案被成功执行时触发
 #[event]
 pub struct StakeProposalExecuted {
@@ -0,0 +1,51 @@
+// dao_program/src/instructions/delegation/delegate.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{error::DaoError, event::VotingPowerDelegated, state::{DaoState, Delegation}};
+
+/// 将质押投票权委托给另一个钱包
+pub fn delegate(ctx: Context<Delegate>) -> Result<()> {
+    let delegator = &ctx.accounts.delegator;
+    let delegate = &ctx.accounts.delegate;
+    require_keys_neq!(delegator.key(), delegate.key(), DaoError::CannotDelegateToSelf);
+
+    let delegation = &mut ctx.accounts.delegation;
+    delegation.dao_state = ctx.accounts.dao_state.key();
+    delegation.delegator = delegator.key();
+    delegation.delegate = delegate.key();
+    delegation.created_at = Clock::get()?.unix_timestamp;
+
+    emit!(VotingPowerDelegated {
+        dao_state: delegation.dao_state,
+        delegation: delegation.key(),
+        delegator: delegation.delegator,
+        delegate: delegation.delegate,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    /// 委托人，即投票权的所有者
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub dao_state: Account<'info, DaoState>,
+
+    /// CHECK: 受托人可以是任意钱包，仅记录其地址
+    pub delegate: UncheckedAccount<'info>,
+
+    /// 委托记录 PDA，每个委托人在一个 DAO 中只能有一个受托人
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [b"delegation", dao_state.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,5 @@
+pub mod delegate;
+pub mod undelegate;
+
+pub use delegate::*;
+pub use undelegate::*;
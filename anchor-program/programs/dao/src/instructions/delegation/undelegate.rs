@@ -0,0 +1,37 @@
+// dao_program/src/instructions/delegation/undelegate.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{event::VotingPowerUndelegated, state::{DaoState, Delegation}};
+
+/// 收回委托出去的投票权
+pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+    let delegation = &ctx.accounts.delegation;
+
+    emit!(VotingPowerUndelegated {
+        dao_state: delegation.dao_state,
+        delegation: delegation.key(),
+        delegator: delegation.delegator,
+        delegate: delegation.delegate,
+    });
+    // 关闭委托账户，返还租金 (由 close = delegator 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"delegation", dao_state.key().as_ref(), delegator.key().as_ref()],
+        bump,
+        has_one = delegator,
+        has_one = dao_state,
+        close = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
@@ -1,23 +1,48 @@
 // file: dao_program/src/instructions/initialize_dao.rs
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use crate::{error::DaoError, event::DaoInitialized, state::DaoState, config::Config};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::{error::DaoError, event::DaoInitialized, state::{DaoState, QuorumMode, VotingMode}, config::Config, utils::validate_governance_mint};
 
 
-// 这是初始化 DAO 的主要函数
+// 这是初始化 DAO 的主要函数。传入 council_mint 时同时创建理事会代币金库
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_dao(
     ctx: Context<InitializeDao>,
     threshold: u8,
     vote_duration: i64,
-    quorum: u32,
+    quorum_mode: QuorumMode,
+    quorum: u64,
     staking_yield_rate: u16,
     pass_threshold_percentage: u8,
-    min_staking_amount: u64
+    min_staking_amount: u64,
+    voting_mode: VotingMode,
+    hold_up_time: i64,
+    execution_window: i64,
+    proposal_deposit: u64,
+    proposal_retention_period: i64,
+    unbonding_period: i64,
+    council_voting_mode: VotingMode,
+    council_quorum: u64,
 ) -> Result<()> {
     // 1. 验证
     require!(threshold > 0, DaoError::InvalidThreshold);
     require!(vote_duration > 0, DaoError::InvalidVoteDuration);
+    require!(
+        pass_threshold_percentage > 0 && pass_threshold_percentage <= 100,
+        DaoError::InvalidPassThreshold
+    );
+    quorum_mode.validate(quorum)?;
+    require!(hold_up_time >= 0, DaoError::InvalidHoldUpTime);
+    require!(execution_window > 0, DaoError::InvalidExecutionWindow);
+    require!(proposal_retention_period >= 0, DaoError::InvalidRetentionPeriod);
+    require!(unbonding_period >= 0, DaoError::InvalidUnbondingPeriod);
+    validate_governance_mint(&ctx.accounts.token_mint.to_account_info())?;
+    if let Some(council_mint) = &ctx.accounts.council_mint {
+        require_keys_neq!(council_mint.key(), ctx.accounts.token_mint.key(), DaoError::InvalidGoverningMint);
+        validate_governance_mint(&council_mint.to_account_info())?;
+        quorum_mode.validate(council_quorum)?;
+    }
 
 
 
@@ -32,10 +57,27 @@ pub fn initialize_dao(
     dao_state.signer = vec![ctx.accounts.authority.key()];
     dao_state.project = Pubkey::default();
     dao_state.total_staked_amount = 0; // 初始化总质押量
+    dao_state.quorum_mode = quorum_mode;
     dao_state.quorum = quorum;
     dao_state.staking_yield_rate = staking_yield_rate;
     dao_state.pass_threshold_percentage = pass_threshold_percentage;
     dao_state.min_staking_amount = min_staking_amount;
+    dao_state.voting_mode = voting_mode;
+    dao_state.governance_rules = Vec::new();
+    dao_state.hold_up_time = hold_up_time;
+    dao_state.execution_window = execution_window;
+    dao_state.proposal_count = 0;
+    dao_state.proposal_deposit = proposal_deposit;
+    dao_state.proposal_retention_period = proposal_retention_period;
+    dao_state.unbonding_period = unbonding_period;
+    dao_state.guardian = None;
+    dao_state.reward_per_token = 0;
+    dao_state.last_reward_update = Clock::get()?.unix_timestamp;
+    dao_state.undistributed_rewards = 0;
+    dao_state.council_mint = ctx.accounts.council_mint.as_ref().map(|mint| mint.key());
+    dao_state.council_voting_mode = council_voting_mode;
+    dao_state.council_quorum = council_quorum;
+    dao_state.council_total_staked_amount = 0;
 
     // 4. 触发 DaoInitialized 事件
     emit!(DaoInitialized {
@@ -45,10 +87,20 @@ pub fn initialize_dao(
         token_mint: dao_state.token_mint,
         threshold: dao_state.threshold,
         vote_duration: dao_state.vote_duration,
+        quorum_mode: dao_state.quorum_mode,
         quorum: dao_state.quorum,
         staking_yield_rate: dao_state.staking_yield_rate,
         pass_threshold_percentage: dao_state.pass_threshold_percentage,
         min_staking_amount: dao_state.min_staking_amount,
+        voting_mode: dao_state.voting_mode,
+        hold_up_time: dao_state.hold_up_time,
+        execution_window: dao_state.execution_window,
+        proposal_deposit: dao_state.proposal_deposit,
+        proposal_retention_period: dao_state.proposal_retention_period,
+        unbonding_period: dao_state.unbonding_period,
+        council_mint: dao_state.council_mint,
+        council_voting_mode: dao_state.council_voting_mode,
+        council_quorum: dao_state.council_quorum,
     });
 
     Ok(())
@@ -77,7 +129,8 @@ pub struct InitializeDao<'info> {
     /// CHECK: 这是一个系统账户PDA，仅用于存储SOL，其安全性由种子保证。
     pub treasury: SystemAccount<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    /// 治理代币，支持 Token Program 与 Token-2022
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// 新增：初始化治理代币金库PDA
     #[account(
@@ -87,8 +140,36 @@ pub struct InitializeDao<'info> {
         bump,
         token::mint = token_mint,
         token::authority = governance_vault, // PDA 自己是自己的 authority
+        token::token_program = token_program,
     )]
-    pub governance_vault: Account<'info, TokenAccount>,
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// 质押奖励金库PDA，奖励以治理代币发放
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault".as_ref(), dao_state.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = reward_vault,
+        token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// 可选的理事会治理代币，需与社区代币属于同一个 Token Program
+    pub council_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// 理事会代币金库PDA，仅在传入 council_mint 时创建
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"council_vault".as_ref(), dao_state.key().as_ref()],
+        bump,
+        token::mint = council_mint,
+        token::authority = council_vault,
+        token::token_program = token_program,
+    )]
+    pub council_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
 
     #[account(
@@ -98,8 +179,8 @@ pub struct InitializeDao<'info> {
     pub config: Account<'info, Config>,
 
     pub system_program: Program<'info, System>,
-    /// 需要 Token Program 来创建金库账户
-    pub token_program: Program<'info, Token>,
+    /// 需要治理代币所属的 Token Program 来创建金库账户
+    pub token_program: Interface<'info, TokenInterface>,
     /// 需要 Rent 来初始化账户
     pub rent: Sysvar<'info, Rent>,
 }
\ No newline at end of file
@@ -5,6 +5,8 @@ pub mod mul_proposal;
 pub mod stake;
 pub mod staker_proposal;
 pub mod claim_payment;
+pub mod delegation;
+pub mod rewards;
 
 pub use initialize_dao::*;
 pub use initialize_config::*;
@@ -13,3 +15,5 @@ pub use mul_proposal::*;
 pub use stake::*;
 pub use staker_proposal::*;
 pub use claim_payment::*;
+pub use delegation::*;
+pub use rewards::*;
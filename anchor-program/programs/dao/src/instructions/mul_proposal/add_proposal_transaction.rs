@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalInstructionsAdded,
+    state::{DaoState, Proposal, ProposalInstruction, ProposalState, ProposalTransaction, ProposalType},
+};
+
+/// 为提案中的 ExecuteInstructions 操作写入待执行的指令，必须在提交审批之前完成
+pub fn add_proposal_transaction(
+    ctx: Context<AddProposalTransaction>,
+    action_index: u8,
+    instructions: Vec<ProposalInstruction>,
+) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(proposal.state == ProposalState::Draft, DaoError::ProposalTransactionLocked);
+
+    let Some(ProposalType::ExecuteInstructions { instruction_count }) =
+        proposal.actions.get(action_index as usize).copied()
+    else {
+        return err!(DaoError::NotAnInstructionProposal);
+    };
+    require!(
+        instructions.len() == instruction_count as usize,
+        DaoError::InvalidInstructionCount
+    );
+    // 禁止回调本程序，避免执行期间 DAO 状态被内层调用覆盖
+    require!(
+        instructions.iter().all(|ix| ix.program_id != crate::ID),
+        DaoError::InvalidProposalInstruction
+    );
+    // 只有国库 PDA 可以作为签名者，防止借用执行者的签名转走其资金
+    let (treasury, _) = Pubkey::find_program_address(
+        &[b"treasury", ctx.accounts.dao_state.key().as_ref()],
+        ctx.program_id,
+    );
+    require!(
+        instructions.iter().all(|ix| ix.only_signed_by(&treasury)),
+        DaoError::InvalidInstructionSigner
+    );
+
+    let proposal_transaction = &mut ctx.accounts.proposal_transaction;
+    proposal_transaction.proposal = proposal.key();
+    proposal_transaction.proposer = proposal.proposer;
+    proposal_transaction.instructions = instructions;
+
+    emit!(ProposalInstructionsAdded {
+        dao_state: ctx.accounts.dao_state.key(),
+        proposal: proposal.key(),
+        action_index,
+        proposal_transaction: proposal_transaction.key(),
+        instruction_count,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action_index: u8, instructions: Vec<ProposalInstruction>)]
+pub struct AddProposalTransaction<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// 存放指令的账户，每个 ExecuteInstructions 操作一个，大小按实际指令内容计算
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalTransaction::space(&instructions),
+        seeds = [b"proposal_transaction".as_ref(), proposal.key().as_ref(), &[action_index]],
+        bump
+    )]
+    pub proposal_transaction: Account<'info, ProposalTransaction>,
+
+    /// 只有提案发起人可以写入指令
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::{ProposalCancelled, ProposalDepositForfeited},
+    state::{DaoState, Proposal, ProposalMetadata, ProposalState},
+    utils::transfer_program_lamports,
+};
+
+/// 发起人在投票开始前取消提案，提案及元数据账户关闭，租金退还给发起人。
+/// 已提交审批的质押者提案押金转入国库，防止垃圾提案发起人抢在多签取消前自行取消、取回押金；
+/// 草稿尚未进入审批，押金随账户一起退还
+pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        matches!(proposal.state, ProposalState::Draft | ProposalState::PendingApproval),
+        DaoError::InvalidProposalState
+    );
+
+    let amount = proposal.deposit_amount;
+    if amount > 0 && proposal.state == ProposalState::PendingApproval {
+        proposal.deposit_amount = 0;
+        transfer_program_lamports(
+            &proposal.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            amount,
+        )?;
+        emit!(ProposalDepositForfeited {
+            dao_state: ctx.accounts.dao_state.key(),
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            proposer: proposal.proposer,
+            cancelled_by: proposal.proposer,
+            amount,
+        });
+    }
+
+    emit!(ProposalCancelled {
+        dao_state: ctx.accounts.dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        actions: proposal.actions.clone(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump,
+        has_one = proposal,
+        close = proposer
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    /// 接收被没收押金的国库
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref(), dao_state.key().as_ref()],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
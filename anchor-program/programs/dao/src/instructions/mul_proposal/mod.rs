@@ -1,5 +1,17 @@
 pub mod mul_propose;
 pub mod mul_approve;
+pub mod mul_reject;
+pub mod cancel_proposal;
+pub mod update_proposal_metadata;
+pub mod add_proposal_transaction;
+pub mod submit_proposal;
+pub mod mul_cancel_spam_propose;
 
 pub use mul_propose::*;
 pub use mul_approve::*;
+pub use mul_reject::*;
+pub use cancel_proposal::*;
+pub use update_proposal_metadata::*;
+pub use add_proposal_transaction::*;
+pub use submit_proposal::*;
+pub use mul_cancel_spam_propose::*;
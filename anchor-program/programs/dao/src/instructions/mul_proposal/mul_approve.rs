@@ -3,7 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{
     error::DaoError,
     event::ProposalApproved,
-    state::{DaoState, Proposal},
+    state::{DaoState, Proposal, ProposalState, VoterPopulation},
 };
 
 pub fn mul_approve_propose(ctx: Context<Approve>) -> Result<()> {
@@ -13,9 +13,11 @@ pub fn mul_approve_propose(ctx: Context<Approve>) -> Result<()> {
     let clock = Clock::get()?;
 
     // 1. 标准的批准验证
-    require!(!proposal.executed, DaoError::ProposalAlreadyExecuted);
+    require!(proposal.state == ProposalState::PendingApproval, DaoError::InvalidProposalState);
     require!(dao_state.signer.contains(&approver.key()), DaoError::UnauthorizedSigner);
     require!(!proposal.approvals.contains(&approver.key()), DaoError::AlreadyApproved);
+    require!(!proposal.rejections.contains(&approver.key()), DaoError::AlreadyRejected);
+    require!(!proposal.spam_votes.contains(&approver.key()), DaoError::AlreadyVotedSpam);
 
     // 2. 添加批准记录
     proposal.approvals.push(approver.key());
@@ -36,10 +38,30 @@ pub fn mul_approve_propose(ctx: Context<Approve>) -> Result<()> {
         msg!("Threshold met for proposal #{}. Entering voting phase...", proposal.proposal_id);
 
         // 达到阈值后，进入投票阶段
+        proposal.state = ProposalState::Voting;
         proposal.approved_at = Some(clock.unix_timestamp);
-        // 设置投票结束时间（例如7天后）
-        proposal.end_time = clock.unix_timestamp + (7 * 24 * 60 * 60); // 7天
-        
+        // 使用批准前一刻的总质押量快照，与投票权重的快照时间一致
+        match proposal.voter_population {
+            VoterPopulation::Community => {
+                proposal.snapshot_total_staked = dao_state.total_staked_at(clock.unix_timestamp)?;
+            }
+            VoterPopulation::Council => {
+                proposal.snapshot_total_staked = dao_state.council_total_staked_at(clock.unix_timestamp)?;
+            }
+            VoterPopulation::Both => {
+                proposal.snapshot_total_staked = dao_state.total_staked_at(clock.unix_timestamp)?;
+                proposal.snapshot_council_total_staked =
+                    dao_state.council_total_staked_at(clock.unix_timestamp)?;
+            }
+        }
+        // 按该类提案的治理规则设置投票结束时间，并锁定计票规则
+        let rule = proposal.governance_rule(dao_state);
+        proposal.snapshot_voting_rule(dao_state);
+        proposal.end_time = clock
+            .unix_timestamp
+            .checked_add(rule.vote_duration)
+            .ok_or(DaoError::ArithmeticOverflow)?;
+
         msg!("Proposal #{} entered voting phase. Voting ends at: {}", 
              proposal.proposal_id, proposal.end_time);
     }
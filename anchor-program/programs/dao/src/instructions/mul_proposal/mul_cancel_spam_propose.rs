@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::{ProposalDepositForfeited, SpamCancellationVoted},
+    state::{DaoState, Proposal, ProposalState},
+    utils::transfer_program_lamports,
+};
+
+/// 多签签名者投票将尚未批准的质押者提案作为垃圾提案取消。
+/// 与批准相同，投票数达到阈值后提案被取消，押金转入国库
+pub fn mul_cancel_spam_propose(ctx: Context<CancelSpamPropose>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let dao_state = &ctx.accounts.dao_state;
+    let signer = &ctx.accounts.signer;
+
+    require!(proposal.state == ProposalState::PendingApproval, DaoError::InvalidProposalState);
+    require!(proposal.deposit_amount > 0, DaoError::NoProposalDeposit);
+    require!(!proposal.spam_votes.contains(&signer.key()), DaoError::AlreadyVotedSpam);
+    require!(!proposal.approvals.contains(&signer.key()), DaoError::AlreadyApproved);
+
+    // 1. 记录垃圾提案投票
+    proposal.spam_votes.push(signer.key());
+    msg!("Proposal #{} marked as spam by: {}. Total spam votes: {}/{}", proposal.proposal_id, signer.key(), proposal.spam_votes.len(), dao_state.threshold);
+
+    emit!(SpamCancellationVoted {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        signer: signer.key(),
+        current_spam_votes: proposal.spam_votes.len() as u64,
+        threshold: dao_state.threshold,
+    });
+
+    // 2. 未达到阈值时只记录投票
+    if (proposal.spam_votes.len() as u8) < dao_state.threshold {
+        return Ok(());
+    }
+
+    let amount = proposal.deposit_amount;
+    proposal.deposit_amount = 0;
+    proposal.state = ProposalState::Cancelled;
+    proposal.finalized_at = Some(Clock::get()?.unix_timestamp);
+    transfer_program_lamports(
+        &proposal.to_account_info(),
+        &ctx.accounts.treasury.to_account_info(),
+        amount,
+    )?;
+
+    msg!("Proposal #{} cancelled as spam by: {}", proposal.proposal_id, signer.key());
+
+    emit!(ProposalDepositForfeited {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        cancelled_by: signer.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelSpamPropose<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref(), dao_state.key().as_ref()],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        constraint = dao_state.signer.contains(&signer.key()) @ DaoError::UnauthorizedSigner
+    )]
+    pub signer: Signer<'info>,
+}
@@ -1,22 +1,154 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::DaoError, event::ProposalCreated, state::{DaoState, DaoUpdateAction, Proposal, ProposalType}};
+use crate::{error::DaoError, event::ProposalCreated, state::{DaoState, DaoUpdateAction, Proposal, ProposalMetadata, ProposalOption, ProposalState, ProposalType, QuorumMode, VoteTally, VoterPopulation, VotingMode, MAX_OPTION_LABEL_LEN, MAX_PROPOSAL_ACTIONS, MAX_PROPOSAL_OPTIONS}};
 
+#[allow(clippy::too_many_arguments)]
 pub fn mul_create_propose(
     ctx: Context<Propose>,
-    proposal_id: u64, 
-    proposal_type: ProposalType,
+    actions: Vec<ProposalType>,
+    options: Vec<ProposalOption>,
+    voter_population: VoterPopulation,
     title: String,
-    description: String
+    description: String,
+    metadata_uri: String,
+    content_hash: [u8; 32],
 ) -> Result<()> {
     
-    let dao_state = &ctx.accounts.dao_state;
+    let dao_state = &mut ctx.accounts.dao_state;
     let proposal = &mut ctx.accounts.proposal;
+    let proposal_metadata = &mut ctx.accounts.proposal_metadata;
     let proposer = &ctx.accounts.proposer;
     let clock = Clock::get()?;
 
-    // 1. 根据不同的 Action 类型进行特定的验证
-    match &proposal_type {
+    // 1. 验证操作数量，并逐个验证每个操作
+    validate_proposal_actions(dao_state, &actions)?;
+    validate_proposal_options(&actions, &options)?;
+    validate_voter_population(dao_state, voter_population, &options)?;
+    ProposalMetadata::validate(&title, &description, &metadata_uri)?;
+
+    // 2. 使用链上计数器分配提案 ID
+    let proposal_id = dao_state.proposal_count;
+    dao_state.proposal_count = proposal_id.checked_add(1).ok_or(DaoError::ArithmeticOverflow)?;
+
+    proposal.dao_state = dao_state.key();
+    proposal.proposer = proposer.key();
+    proposal.actions = actions.clone();
+    proposal.executed_actions = 0;
+    proposal.options = options.clone();
+    proposal.voter_population = voter_population;
+    proposal.approvals = Vec::new();
+    proposal.rejections = Vec::new();
+    proposal.spam_votes = Vec::new();
+    proposal.state = ProposalState::Draft; // 发起人提交后进入审批
+    proposal.proposal_id = proposal_id;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.approved_at = None;
+    proposal.snapshot_total_staked = 0;
+    proposal.council_votes = VoteTally::default();
+    proposal.snapshot_council_total_staked = 0;
+    proposal.quorum_mode = QuorumMode::default();
+    proposal.quorum = 0;
+    proposal.council_quorum = 0;
+    proposal.pass_threshold_percentage = 0;
+    proposal.voting_mode = VotingMode::default();
+    proposal.council_voting_mode = VotingMode::default();
+    proposal.executable_at = 0;
+    proposal.execution_deadline = 0;
+    proposal.deposit_amount = 0;
+    proposal.finalized_at = None;
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.abstain_votes = 0;
+    proposal.voter_count = 0;
+    proposal.raw_votes = 0;
+    proposal.end_time = 0; // 多签批准后设置
+
+    // 标题、描述等元数据单独存放，账户大小按实际文本计算
+    proposal_metadata.proposal = proposal.key();
+    proposal_metadata.title = title.clone();
+    proposal_metadata.description = description;
+    proposal_metadata.metadata_uri = metadata_uri.clone();
+    proposal_metadata.content_hash = content_hash;
+
+    // 触发统一事件
+    emit!(ProposalCreated {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id,
+        proposer: proposer.key(),
+        actions, // 这里使用的是 clone 之前的原始变量
+        options,
+        voter_population,
+        proposal_metadata: proposal_metadata.key(),
+        title,
+        metadata_uri,
+        content_hash,
+    });
+    Ok(())
+}
+
+/// 验证操作数量，并逐个验证每个操作
+pub(crate) fn validate_proposal_actions(dao_state: &DaoState, actions: &[ProposalType]) -> Result<()> {
+    require!(
+        !actions.is_empty() && actions.len() <= MAX_PROPOSAL_ACTIONS,
+        DaoError::InvalidActionCount
+    );
+    for action in actions.iter() {
+        validate_proposal_action(dao_state, action)?;
+    }
+    Ok(())
+}
+
+/// 验证多选提案的选项：选项数量在 2..=MAX_PROPOSAL_OPTIONS 之间，
+/// 每个操作恰好属于一个选项，允许存在不执行操作的选项。为空时为普通提案
+pub(crate) fn validate_proposal_options(actions: &[ProposalType], options: &[ProposalOption]) -> Result<()> {
+    if options.is_empty() {
+        return Ok(());
+    }
+    require!(
+        options.len() >= 2 && options.len() <= MAX_PROPOSAL_OPTIONS,
+        DaoError::InvalidProposalOptions
+    );
+
+    let mut referenced = vec![false; actions.len()];
+    for option in options.iter() {
+        require!(
+            !option.label.is_empty() && option.label.len() <= MAX_OPTION_LABEL_LEN,
+            DaoError::InvalidProposalOptions
+        );
+        require!(
+            option.votes == 0 && option.voter_count == 0 && option.raw_votes == 0,
+            DaoError::InvalidProposalOptions
+        );
+        if let Some(index) = option.action_index {
+            let slot = referenced.get_mut(index as usize).ok_or(DaoError::InvalidProposalOptions)?;
+            require!(!*slot, DaoError::InvalidProposalOptions);
+            *slot = true;
+        }
+    }
+    require!(referenced.iter().all(|r| *r), DaoError::InvalidProposalOptions);
+    Ok(())
+}
+
+/// 验证提案的投票人群：理事会参与投票需要 DAO 配置了理事会代币；
+/// 两者共同投票时分别计赞成/反对票，不支持多选提案
+pub(crate) fn validate_voter_population(
+    dao_state: &DaoState,
+    voter_population: VoterPopulation,
+    options: &[ProposalOption],
+) -> Result<()> {
+    if voter_population != VoterPopulation::Community {
+        require!(dao_state.council_mint.is_some(), DaoError::CouncilNotConfigured);
+    }
+    if voter_population == VoterPopulation::Both {
+        require!(options.is_empty(), DaoError::InvalidVoterPopulation);
+    }
+    Ok(())
+}
+
+/// 根据不同的 Action 类型进行特定的验证
+pub(crate) fn validate_proposal_action(dao_state: &DaoState, action: &ProposalType) -> Result<()> {
+    match action {
 
         //定期支付
         ProposalType::AddRecurringPayment { 
@@ -62,6 +194,68 @@ pub fn mul_create_propose(
                         DaoError::InvalidNewThreshold
                     );
                 }
+                DaoUpdateAction::ChangeTimelock { hold_up_time, execution_window } => {
+                    require!(*hold_up_time >= 0, DaoError::InvalidHoldUpTime);
+                    require!(*execution_window > 0, DaoError::InvalidExecutionWindow);
+                }
+                DaoUpdateAction::AppointGuardian { guardian } => {
+                    require!(*guardian != Pubkey::default(), DaoError::InvalidGuardian);
+                }
+                DaoUpdateAction::RemoveGuardian => {
+                    require!(dao_state.guardian.is_some(), DaoError::GuardianNotSet);
+                }
+                DaoUpdateAction::ChangeProposalDeposit { .. } => {}
+                DaoUpdateAction::ChangeQuorum { quorum_mode, quorum } => {
+                    // 验证：默认值、已配置规则和理事会的法定人数在新模式下都必须有效
+                    quorum_mode.validate(*quorum)?;
+                    for rule in dao_state.governance_rules.iter() {
+                        quorum_mode.validate(rule.quorum)?;
+                    }
+                    if dao_state.council_mint.is_some() {
+                        quorum_mode.validate(dao_state.council_quorum)?;
+                    }
+                }
+                DaoUpdateAction::ChangeVoteDuration { vote_duration } => {
+                    require!(*vote_duration > 0, DaoError::InvalidVoteDuration);
+                }
+                DaoUpdateAction::ChangeStakingYieldRate { .. } => {}
+                DaoUpdateAction::ChangePassThreshold { pass_threshold_percentage } => {
+                    require!(
+                        *pass_threshold_percentage > 0 && *pass_threshold_percentage <= 100,
+                        DaoError::InvalidPassThreshold
+                    );
+                }
+                DaoUpdateAction::ChangeMinStakingAmount { .. } => {}
+                DaoUpdateAction::ChangeVotingMode { .. } => {}
+                DaoUpdateAction::ChangeCouncilVotingMode { .. } => {
+                    require!(dao_state.council_mint.is_some(), DaoError::CouncilNotConfigured);
+                }
+                DaoUpdateAction::ChangeCouncilQuorum { council_quorum } => {
+                    require!(dao_state.council_mint.is_some(), DaoError::CouncilNotConfigured);
+                    dao_state.quorum_mode.validate(*council_quorum)?;
+                }
+                DaoUpdateAction::ChangeProposalRetention { proposal_retention_period } => {
+                    require!(*proposal_retention_period >= 0, DaoError::InvalidRetentionPeriod);
+                }
+                DaoUpdateAction::ChangeUnbondingPeriod { unbonding_period } => {
+                    require!(*unbonding_period >= 0, DaoError::InvalidUnbondingPeriod);
+                }
+                DaoUpdateAction::SetGovernanceRule { rule } => {
+                    // 验证：规则参数与初始化时的约束一致
+                    require!(rule.vote_duration > 0, DaoError::InvalidVoteDuration);
+                    dao_state.quorum_mode.validate(rule.quorum)?;
+                    require!(
+                        rule.pass_threshold_percentage > 0 && rule.pass_threshold_percentage <= 100,
+                        DaoError::InvalidPassThreshold
+                    );
+                }
+                DaoUpdateAction::RemoveGovernanceRule { kind } => {
+                    // 验证：只能移除已配置的规则
+                    require!(
+                        dao_state.governance_rules.iter().any(|r| r.kind == *kind),
+                        DaoError::GovernanceRuleNotFound
+                    );
+                }
             }
         },
         // 国库提款
@@ -74,49 +268,42 @@ pub fn mul_create_propose(
             // 验证：收款人不能是国库本身，防止资金被锁
             require!(*recipient != dao_state.treasury, DaoError::InvalidRecipient);
         },
+        // 任意指令执行，指令内容通过 add_proposal_transaction 写入
+        ProposalType::ExecuteInstructions { instruction_count } => {
+            require!(*instruction_count > 0, DaoError::InvalidInstructionCount);
+        },
+        // 国库向奖励金库注资
+        ProposalType::FundRewards { amount } => {
+            require!(*amount > 0, DaoError::InvalidRewardAmount);
+        },
     }
-
-    proposal.dao_state = dao_state.key();
-    proposal.proposer = proposer.key();
-    proposal.proposal_type = proposal_type;
-    proposal.approvals = Vec::new();
-    proposal.executed = false;
-    proposal.proposal_id = proposal_id;
-    proposal.title = title;
-    proposal.description = description;
-    proposal.created_at = clock.unix_timestamp;
-    proposal.approved_at = None;
-    proposal.yes_votes = 0;
-    proposal.no_votes = 0;
-    proposal.voter_count = 0;
-    proposal.end_time = 0; // 多签批准后设置
-
-    // 触发统一事件
-    emit!(ProposalCreated {
-        dao_state: dao_state.key(),
-        proposal: proposal.key(),
-        proposal_id,
-        proposer: proposer.key(),
-        proposal_type: proposal_type, // 这里使用的是 clone 之前的原始变量
-    });
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(proposal_id: u64)]
+#[instruction(actions: Vec<ProposalType>, options: Vec<ProposalOption>, voter_population: VoterPopulation, title: String, description: String, metadata_uri: String)]
 pub struct Propose<'info> {
-    #[account(has_one = authority)]
+    #[account(mut, has_one = authority)]
     pub dao_state: Account<'info, DaoState>,
 
     #[account(
         init,
         payer = proposer,
-        space = 8 + 32 + 32 + 100 + (4 + 5 * 32) + 1 + 8, // 预留足够空间
-        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal_id.to_le_bytes()],
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &dao_state.proposal_count.to_le_bytes()],
         bump
     )]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalMetadata::space(&title, &description, &metadata_uri),
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
     #[account(
         mut,
         constraint = dao_state.signer.contains(&proposer.key()) @ DaoError::UnauthorizedSigner
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalRejected,
+    state::{DaoState, Proposal, ProposalMetadata, ProposalState},
+};
+
+pub fn mul_reject_propose(ctx: Context<Reject>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let dao_state = &ctx.accounts.dao_state;
+    let rejector = &ctx.accounts.rejector;
+
+    // 1. 与批准相同的验证，已批准或已否决的签名者不能再否决
+    require!(proposal.state == ProposalState::PendingApproval, DaoError::InvalidProposalState);
+    require!(dao_state.signer.contains(&rejector.key()), DaoError::UnauthorizedSigner);
+    require!(!proposal.rejections.contains(&rejector.key()), DaoError::AlreadyRejected);
+    require!(!proposal.approvals.contains(&rejector.key()), DaoError::AlreadyApproved);
+
+    // 2. 添加否决记录
+    proposal.rejections.push(rejector.key());
+    msg!("Proposal #{} rejected by: {}. Total rejections: {}", proposal.proposal_id, rejector.key(), proposal.rejections.len());
+
+    // 3. 剩余未否决的签名者不足以达到阈值时，提案被否决
+    let remaining_signers = dao_state.signer.len().saturating_sub(proposal.rejections.len());
+    let rejected = (remaining_signers as u8) < dao_state.threshold;
+    if rejected {
+        proposal.state = ProposalState::Rejected;
+    }
+
+    emit!(ProposalRejected {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        rejector: rejector.key(),
+        current_rejections: proposal.rejections.len() as u64,
+        threshold: dao_state.threshold,
+        state: proposal.state,
+    });
+
+    // 4. 被否决的提案及其元数据直接关闭，租金和押金退还给发起人。
+    //    否决需要多名签名者共同完成，发起人无法自行触发；
+    //    多签认为是垃圾提案时应使用 mul_cancel_spam_propose 没收押金
+    if rejected {
+        msg!("Proposal #{} can no longer reach the threshold and is closed.", proposal.proposal_id);
+        proposal.close(ctx.accounts.proposer.to_account_info())?;
+        ctx.accounts.proposal_metadata.close(ctx.accounts.proposer.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Reject<'info> {
+    #[account(has_one = authority)]
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump,
+        has_one = proposal
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    pub rejector: Signer<'info>,
+
+    /// CHECK: 提案发起人，提案被否决时接收退还的租金，由 has_one 约束校验
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: DAO 的 authority，用于 has_one 约束
+    pub authority: AccountInfo<'info>,
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalSubmitted,
+    state::{DaoState, Proposal, ProposalState, ProposalTransaction, ProposalType},
+};
+
+/// 发起人提交草稿，提案进入多签审批。提交后不能再写入指令。
+///
+/// 每个 ExecuteInstructions 操作的 ProposalTransaction 按操作顺序通过 remaining_accounts 传入，
+/// 确保进入审批的提案在通过后可以执行
+pub fn submit_proposal(ctx: Context<SubmitProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(proposal.state == ProposalState::Draft, DaoError::InvalidProposalState);
+
+    let proposal_key = proposal.key();
+    let mut transaction_infos = ctx.remaining_accounts.iter();
+    for (index, action) in proposal.actions.iter().enumerate() {
+        let ProposalType::ExecuteInstructions { instruction_count } = action else {
+            continue;
+        };
+        let transaction_info = transaction_infos.next().ok_or(DaoError::MissingProposalTransaction)?;
+        let (expected_transaction, _) = Pubkey::find_program_address(
+            &[b"proposal_transaction", proposal_key.as_ref(), &[index as u8]],
+            ctx.program_id,
+        );
+        require_keys_eq!(transaction_info.key(), expected_transaction, DaoError::InvalidProposalTransaction);
+        require!(
+            transaction_info.owner == ctx.program_id && !transaction_info.data_is_empty(),
+            DaoError::MissingProposalTransaction
+        );
+        let proposal_transaction =
+            ProposalTransaction::try_deserialize(&mut &transaction_info.try_borrow_data()?[..])?;
+        require!(
+            proposal_transaction.instructions.len() == *instruction_count as usize,
+            DaoError::InvalidInstructionCount
+        );
+    }
+
+    proposal.state = ProposalState::PendingApproval;
+
+    emit!(ProposalSubmitted {
+        dao_state: ctx.accounts.dao_state.key(),
+        proposal: proposal_key,
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitProposal<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// 只有提案发起人可以提交
+    pub proposer: Signer<'info>,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalMetadataUpdated,
+    state::{DaoState, Proposal, ProposalMetadata, ProposalState},
+};
+
+/// 发起人修改提案元数据，必须在第一次批准之前完成。账户大小随新文本重新分配
+pub fn update_proposal_metadata(
+    ctx: Context<UpdateProposalMetadata>,
+    title: String,
+    description: String,
+    metadata_uri: String,
+    content_hash: [u8; 32],
+) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        matches!(proposal.state, ProposalState::Draft | ProposalState::PendingApproval),
+        DaoError::InvalidProposalState
+    );
+    require!(proposal.approvals.is_empty(), DaoError::ProposalMetadataLocked);
+    ProposalMetadata::validate(&title, &description, &metadata_uri)?;
+
+    let proposal_metadata = &mut ctx.accounts.proposal_metadata;
+    proposal_metadata.title = title;
+    proposal_metadata.description = description;
+    proposal_metadata.metadata_uri = metadata_uri;
+    proposal_metadata.content_hash = content_hash;
+
+    emit!(ProposalMetadataUpdated {
+        dao_state: ctx.accounts.dao_state.key(),
+        proposal: proposal.key(),
+        proposal_metadata: proposal_metadata.key(),
+        title: proposal_metadata.title.clone(),
+        description: proposal_metadata.description.clone(),
+        metadata_uri: proposal_metadata.metadata_uri.clone(),
+        content_hash,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(title: String, description: String, metadata_uri: String)]
+pub struct UpdateProposalMetadata<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump,
+        has_one = proposal,
+        realloc = ProposalMetadata::space(&title, &description, &metadata_uri),
+        realloc::payer = proposer,
+        realloc::zero = false
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    /// 只有提案发起人可以修改元数据
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
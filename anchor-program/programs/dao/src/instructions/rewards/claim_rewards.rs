@@ -0,0 +1,92 @@
+// dao_program/src/instructions/rewards/claim_rewards.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{event::RewardsClaimed, state::{DaoState, StakeAccount}};
+
+/// 领取按 staking_yield_rate 累计的质押奖励
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let dao_state = &mut ctx.accounts.dao_state;
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // 1. 累计并结算奖励
+    dao_state.update_rewards(Clock::get()?.unix_timestamp)?;
+    stake_account.settle_rewards(dao_state.reward_per_token)?;
+
+    let amount = stake_account.pending_rewards;
+    require!(amount > 0, crate::error::DaoError::NoRewardsToClaim);
+    stake_account.pending_rewards = 0;
+
+    // 2. 从奖励金库发放
+    let dao_key = dao_state.key();
+    let vault_seeds = &[
+        b"reward_vault".as_ref(),
+        dao_key.as_ref(),
+        &[ctx.bumps.reward_vault],
+    ];
+    let signer = &[&vault_seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.staker_token_account.to_account_info(),
+        authority: ctx.accounts.reward_vault.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_context = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer);
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.token_mint.decimals)?;
+
+    emit!(RewardsClaimed {
+        dao_state: dao_key,
+        stake_account: stake_account.key(),
+        staker: stake_account.staker,
+        amount,
+    });
+
+    // 3. 已全部赎回的质押账户在领取奖励后关闭
+    if stake_account.amount == 0 {
+        stake_account.close(ctx.accounts.staker.to_account_info())?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(mut)]
+    pub dao_state: Account<'info, DaoState>,
+
+    /// 质押者的代币账户，奖励将存入这里。
+    #[account(
+        mut,
+        constraint = staker_token_account.owner == staker.key(),
+        constraint = staker_token_account.mint == dao_state.token_mint
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 治理代币，transfer_checked 需要校验精度
+    #[account(address = dao_state.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// 质押奖励金库，奖励将从这里转出。
+    #[account(
+        mut,
+        seeds = [b"reward_vault", dao_state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_account", dao_state.key().as_ref(), staker.key().as_ref()],
+        bump,
+        has_one = staker,
+        has_one = dao_state
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,75 @@
+// dao_program/src/instructions/rewards/fund_rewards.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{event::RewardsFunded, state::DaoState, utils::transfer_into_vault};
+
+/// 向奖励金库注入治理代币，任何人都可以注资。国库通过 FundRewards 提案注资；
+/// 直接转入奖励金库的代币不计入待分配奖励，不会被发放
+pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+    require!(amount > 0, crate::error::DaoError::InvalidRewardAmount);
+
+    // 1. 先按注资前的余额累计奖励，金库耗尽期间不会补发
+    ctx.accounts.dao_state.update_rewards(Clock::get()?.unix_timestamp)?;
+
+    // 2. 将代币转入奖励金库，按实际到账数量计入待分配奖励
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let amount = transfer_into_vault(
+        CpiContext::new(cpi_program, cpi_accounts),
+        &mut ctx.accounts.reward_vault,
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    require!(amount > 0, crate::error::DaoError::InvalidRewardAmount);
+
+    let dao_state = &mut ctx.accounts.dao_state;
+
+    dao_state.undistributed_rewards = dao_state.undistributed_rewards
+        .checked_add(amount)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+
+    emit!(RewardsFunded {
+        dao_state: dao_state.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        undistributed_rewards: dao_state.undistributed_rewards,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(mut)]
+    pub dao_state: Account<'info, DaoState>,
+
+    /// 注资者的代币账户，代币将从这里转出。
+    #[account(
+        mut,
+        constraint = funder_token_account.mint == dao_state.token_mint
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 治理代币，transfer_checked 需要校验精度
+    #[account(address = dao_state.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// 质押奖励金库。
+    #[account(
+        mut,
+        seeds = [b"reward_vault", dao_state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
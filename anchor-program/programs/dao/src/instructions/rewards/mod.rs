@@ -0,0 +1,5 @@
+pub mod fund_rewards;
+pub mod claim_rewards;
+
+pub use fund_rewards::*;
+pub use claim_rewards::*;
@@ -0,0 +1,53 @@
+// dao_program/src/instructions/extend_lock.rs
+
+use anchor_lang::prelude::*;
+
+use crate::{event::LockExtended, state::{DaoState, GoverningToken, StakeAccount, MAX_LOCK_DURATION}};
+
+/// 将质押锁仓延长到 now + lock_duration，新的到期时间必须晚于当前到期时间
+pub fn extend_lock(ctx: Context<ExtendLock>, lock_duration: i64, _governing_token: GoverningToken) -> Result<()> {
+    require!(
+        lock_duration > 0 && lock_duration <= MAX_LOCK_DURATION,
+        crate::error::DaoError::InvalidLockDuration
+    );
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    require!(stake_account.amount > 0, crate::error::DaoError::NoTokensStaked);
+
+    let now = Clock::get()?.unix_timestamp;
+    let new_lock_end = now
+        .checked_add(lock_duration)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    let old_lock_end = stake_account.lock_end;
+    require!(new_lock_end > old_lock_end, crate::error::DaoError::LockNotExtended);
+
+    stake_account.set_lock(now, new_lock_end);
+
+    emit!(LockExtended {
+        dao_state: ctx.accounts.dao_state.key(),
+        stake_account: stake_account.key(),
+        staker: stake_account.staker,
+        old_lock_end,
+        new_lock_end,
+        lock_multiplier: stake_account.lock_multiplier,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(lock_duration: i64, governing_token: GoverningToken)]
+pub struct ExtendLock<'info> {
+    pub staker: Signer<'info>,
+
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [governing_token.stake_seed(), dao_state.key().as_ref(), staker.key().as_ref()],
+        bump,
+        has_one = staker,
+        has_one = dao_state
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+}
@@ -1,5 +1,10 @@
+#[allow(clippy::module_inception)]
 pub mod stake;
 pub mod unstake;
+pub mod withdraw_unbonded;
+pub mod extend_lock;
 
 pub use stake::*;
-pub use unstake::*;
\ No newline at end of file
+pub use unstake::*;
+pub use withdraw_unbonded::*;
+pub use extend_lock::*;
\ No newline at end of file
@@ -1,49 +1,91 @@
 // dao_program/src/instructions/stake.rs
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::{event::TokensStaked, state::{DaoState, StakeAccount}};
+use crate::{event::TokensStaked, state::{DaoState, GoverningToken, StakeAccount, MAX_LOCK_DURATION}, utils::transfer_into_vault};
 
-/// 用户质押治理代币
-pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+/// 用户质押治理代币。可选的锁仓时长会提高投票权重，锁仓期间不可赎回；
+/// 锁仓作用于整个质押账户，只能延长不能缩短。
+/// 社区代币和理事会代币使用各自的金库和质押账户，只有社区代币参与质押奖励
+pub fn stake(
+    ctx: Context<Stake>,
+    amount: u64,
+    lock_duration: Option<i64>,
+    governing_token: GoverningToken,
+) -> Result<()> {
     require!(amount > 0, crate::error::DaoError::InvalidStakeAmount);
+    if let Some(duration) = lock_duration {
+        require!(
+            duration > 0 && duration <= MAX_LOCK_DURATION,
+            crate::error::DaoError::InvalidLockDuration
+        );
+    }
 
-    let dao_state = &mut ctx.accounts.dao_state;
-    let stake_account = &mut ctx.accounts.stake_account;
-
-    // 1. 将代币从用户账户转移到治理金库
-    let cpi_accounts = Transfer {
+    // 1. 将代币从用户账户转移到治理金库，按金库实际收到的数量记账（Token-2022 可能收取转账手续费）
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.staker_token_account.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.governance_vault.to_account_info(),
         authority: ctx.accounts.staker.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_context, amount)?;
+    let amount = transfer_into_vault(
+        cpi_context,
+        &mut ctx.accounts.governance_vault,
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    require!(amount > 0, crate::error::DaoError::InvalidStakeAmount);
+
+    let dao_state = &mut ctx.accounts.dao_state;
+    let stake_account = &mut ctx.accounts.stake_account;
 
-    // 2. 初始化或更新质押账户信息
+    // 2. 初始化或更新质押账户信息，数量变化前先结算奖励
+    let now = Clock::get()?.unix_timestamp;
     if stake_account.staker == Pubkey::default() {
         stake_account.staker = ctx.accounts.staker.key();
         stake_account.dao_state = dao_state.key();
+        stake_account.governing_token = governing_token;
+    }
+    if governing_token == GoverningToken::Community {
+        dao_state.update_rewards(now)?;
+        stake_account.settle_rewards(dao_state.reward_per_token)?;
+    }
+    stake_account.amount = stake_account.amount
+        .checked_add(amount)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    if let Some(duration) = lock_duration {
+        let lock_end = now
+            .checked_add(duration)
+            .ok_or(crate::error::DaoError::ArithmeticOverflow)?
+            .max(stake_account.lock_end);
+        stake_account.set_lock(now, lock_end);
     }
-    stake_account.amount = stake_account.amount.checked_add(amount).unwrap();
 
     // 3. 更新 DAO 的总质押量
-    dao_state.total_staked_amount = dao_state.total_staked_amount.checked_add(amount).unwrap();
+    dao_state.change_total_staked(governing_token, amount, true, now)?;
+
+    // 4. 记录质押快照，供投票时按快照时间计算权重
+    stake_account.record_checkpoint(now);
     
     emit!(TokensStaked{
         dao_state: dao_state.key(),
         stake_account: stake_account.key(),
         staker: stake_account.staker,
+        governing_token,
         amount_staked: amount,
         new_total_for_staker: stake_account.amount,
+        lock_end: stake_account.lock_end,
+        lock_multiplier: stake_account.lock_multiplier_at(now) as u16,
     });
     
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, lock_duration: Option<i64>, governing_token: GoverningToken)]
 pub struct Stake<'info> {
     /// 交易的发起者和签名者，即进行质押的用户。
     #[account(mut)]
@@ -58,29 +100,35 @@ pub struct Stake<'info> {
     #[account(
         mut,
         constraint = staker_token_account.owner == staker.key(),
-        constraint = staker_token_account.mint == dao_state.token_mint
+        constraint = staker_token_account.mint == token_mint.key()
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 所质押的治理代币，transfer_checked 需要校验精度
+    #[account(
+        constraint = dao_state.governing_mint(governing_token) == Some(token_mint.key()) @ crate::error::DaoError::InvalidGoverningMint
     )]
-    pub staker_token_account: Account<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
-    /// DAO 的治理质押金库，一个用于存放所有质押代币的 PDA 代币账户。
+    /// 所质押代币的治理金库，一个用于存放所有质押代币的 PDA 代币账户。
     #[account(
         mut,
-        seeds = [b"governance_vault", dao_state.key().as_ref()],
+        seeds = [governing_token.vault_seed(), dao_state.key().as_ref()],
         bump
     )]
-    pub governance_vault: Account<'info, TokenAccount>,
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// 记录用户质押信息的 PDA 数据账户。
-    /// 每个用户只有一个质押账户。如果是首次质押，则创建此账户。
+    /// 每个用户每种治理代币只有一个质押账户。如果是首次质押，则创建此账户。
     #[account(
         init_if_needed,
         payer = staker,
         space = 8 + StakeAccount::INIT_SPACE,
-        seeds = [b"stake_account", dao_state.key().as_ref(), staker.key().as_ref()],
+        seeds = [governing_token.stake_seed(), dao_state.key().as_ref(), staker.key().as_ref()],
         bump
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
\ No newline at end of file
@@ -1,50 +1,74 @@
 // dao_program/src/instructions/unstake.rs
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-use crate::{event::TokensUnstaked, state::{DaoState, StakeAccount}};
+use crate::{event::TokensUnstaked, state::{DaoState, GoverningToken, StakeAccount, UnbondingAccount}};
 
-/// 用户赎回所有已质押的治理代币
-pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+/// 用户赎回部分或全部已质押的治理代币，余额归零时关闭质押账户。
+/// 赎回的代币进入解绑期，期满后通过 withdraw_unbonded 提取
+pub fn unstake(ctx: Context<Unstake>, amount: u64, governing_token: GoverningToken) -> Result<()> {
     let dao_state = &mut ctx.accounts.dao_state;
-    let stake_account = &ctx.accounts.stake_account;
-    let amount_to_unstake = stake_account.amount;
+    let stake_account = &mut ctx.accounts.stake_account;
+    let unbonding_account = &mut ctx.accounts.unbonding_account;
+    let amount_to_unstake = amount;
 
-    require!(amount_to_unstake > 0, crate::error::DaoError::NoTokensStaked);
+    require!(amount_to_unstake > 0, crate::error::DaoError::InvalidStakeAmount);
+    require!(stake_account.amount > 0, crate::error::DaoError::NoTokensStaked);
+    require!(
+        amount_to_unstake <= stake_account.amount,
+        crate::error::DaoError::InsufficientStake
+    );
+    let now = Clock::get()?.unix_timestamp;
+    // 仍有投票中的提案或锁仓未到期时不允许赎回
+    require!(
+        now >= stake_account.locked_until && now >= stake_account.lock_end,
+        crate::error::DaoError::StakeLocked
+    );
 
-    // 1. 从治理金库将代币转回给用户
-    let dao_key = dao_state.key();
-    let vault_seeds = &[
-        b"governance_vault".as_ref(),
-        dao_key.as_ref(),
-        &[ctx.bumps.governance_vault],
-    ];
-    let signer = &[&vault_seeds[..]];
+    // 1. 更新用户和 DAO 的质押量，解绑中的代币不再计入投票权重、总质押量和奖励
+    if governing_token == GoverningToken::Community {
+        dao_state.update_rewards(now)?;
+        stake_account.settle_rewards(dao_state.reward_per_token)?;
+    }
+    stake_account.amount = stake_account.amount
+        .checked_sub(amount_to_unstake)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    stake_account.record_checkpoint(now);
+    dao_state.change_total_staked(governing_token, amount_to_unstake, false, now)?;
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.governance_vault.to_account_info(),
-        to: ctx.accounts.staker_token_account.to_account_info(),
-        authority: ctx.accounts.governance_vault.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_context = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer);
-    token::transfer(cpi_context, amount_to_unstake)?;
-
-    // 2. 更新 DAO 的总质押量
-    dao_state.total_staked_amount = dao_state.total_staked_amount.checked_sub(amount_to_unstake).unwrap();
+    // 2. 将代币移入解绑记录，解绑截止时间从本次赎回重新计算
+    if unbonding_account.staker == Pubkey::default() {
+        unbonding_account.staker = ctx.accounts.staker.key();
+        unbonding_account.dao_state = dao_state.key();
+    }
+    unbonding_account.amount = unbonding_account.amount
+        .checked_add(amount_to_unstake)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    unbonding_account.release_at = now
+        .checked_add(dao_state.unbonding_period)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
 
     emit!(TokensUnstaked{
         dao_state: dao_state.key(),
         stake_account: stake_account.key(),
         staker: stake_account.staker,
+        governing_token,
         amount_unstaked: amount_to_unstake,
+        remaining_amount: stake_account.amount,
+        unbonding_amount: unbonding_account.amount,
+        release_at: unbonding_account.release_at,
     });
-    // 3. 关闭质押账户，返还租金 (由 close = staker 宏自动处理)
+
+    // 3. 全部赎回且奖励已领取时关闭质押账户，返还租金；
+    //    仍有未领取的奖励时保留账户，由 claim_rewards 关闭
+    if stake_account.amount == 0 && stake_account.pending_rewards == 0 {
+        stake_account.close(ctx.accounts.staker.to_account_info())?;
+    }
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(amount: u64, governing_token: GoverningToken)]
 pub struct Unstake<'info> {
     /// 交易的发起者和签名者，即赎回代币的用户。
     #[account(mut)]
@@ -53,34 +77,25 @@ pub struct Unstake<'info> {
     #[account(mut)]
     pub dao_state: Account<'info, DaoState>,
 
-    /// 质押者的个人代币账户，赎回的代币将存入这里。
+    /// 用户的个人质押记录。余额和待领取奖励归零时在指令中关闭，并将租金返还给 `staker`。
     #[account(
         mut,
-        constraint = staker_token_account.owner == staker.key(),
-        constraint = staker_token_account.mint == dao_state.token_mint
+        seeds = [governing_token.stake_seed(), dao_state.key().as_ref(), staker.key().as_ref()],
+        bump,
+        has_one = staker,
+        has_one = dao_state
     )]
-    pub staker_token_account: Account<'info, TokenAccount>,
+    pub stake_account: Account<'info, StakeAccount>,
 
-    /// 治理质押金库，代币将从这里转出。
+    /// 用户该种治理代币的解绑记录，首次赎回时创建。
     #[account(
-        mut,
-        seeds = [b"governance_vault", dao_state.key().as_ref()],
+        init_if_needed,
+        payer = staker,
+        space = 8 + UnbondingAccount::INIT_SPACE,
+        seeds = [governing_token.unbonding_seed(), dao_state.key().as_ref(), staker.key().as_ref()],
         bump
     )]
-    pub governance_vault: Account<'info, TokenAccount>,
-
-    /// 用户的个人质押记录。
-    /// `close` 属性表示在指令成功执行后，关闭该账户并将租金返还给 `staker`。
-    #[account(
-        mut,
-        seeds = [b"stake_account", dao_state.key().as_ref(), staker.key().as_ref()],
-        bump,
-        has_one = staker,
-        has_one = dao_state,
-        close = staker
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
+    pub unbonding_account: Account<'info, UnbondingAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,90 @@
+// dao_program/src/instructions/withdraw_unbonded.rs
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{event::UnbondedTokensWithdrawn, state::{DaoState, GoverningToken, UnbondingAccount}};
+
+/// 解绑期结束后，将解绑中的代币从治理金库转回给用户
+pub fn withdraw_unbonded(ctx: Context<WithdrawUnbonded>, governing_token: GoverningToken) -> Result<()> {
+    let dao_state = &ctx.accounts.dao_state;
+    let unbonding_account = &ctx.accounts.unbonding_account;
+    let amount = unbonding_account.amount;
+
+    require!(amount > 0, crate::error::DaoError::NothingToWithdraw);
+    require!(
+        Clock::get()?.unix_timestamp >= unbonding_account.release_at,
+        crate::error::DaoError::UnbondingNotComplete
+    );
+
+    // 1. 从治理金库将代币转回给用户
+    let dao_key = dao_state.key();
+    let vault_seeds = &[
+        governing_token.vault_seed(),
+        dao_key.as_ref(),
+        &[ctx.bumps.governance_vault],
+    ];
+    let signer = &[&vault_seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.governance_vault.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.staker_token_account.to_account_info(),
+        authority: ctx.accounts.governance_vault.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_context = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer);
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.token_mint.decimals)?;
+
+    emit!(UnbondedTokensWithdrawn {
+        dao_state: dao_key,
+        staker: unbonding_account.staker,
+        governing_token,
+        amount,
+    });
+    // 2. 关闭解绑记录，返还租金 (由 close = staker 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(governing_token: GoverningToken)]
+pub struct WithdrawUnbonded<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub dao_state: Account<'info, DaoState>,
+
+    /// 质押者的个人代币账户，提取的代币将存入这里。
+    #[account(
+        mut,
+        constraint = staker_token_account.owner == staker.key(),
+        constraint = staker_token_account.mint == token_mint.key()
+    )]
+    pub staker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 所质押的治理代币，transfer_checked 需要校验精度
+    #[account(
+        constraint = dao_state.governing_mint(governing_token) == Some(token_mint.key()) @ crate::error::DaoError::InvalidGoverningMint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// 该种治理代币的金库，代币将从这里转出。
+    #[account(
+        mut,
+        seeds = [governing_token.vault_seed(), dao_state.key().as_ref()],
+        bump
+    )]
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [governing_token.unbonding_seed(), dao_state.key().as_ref(), staker.key().as_ref()],
+        bump,
+        has_one = staker,
+        has_one = dao_state,
+        close = staker
+    )]
+    pub unbonding_account: Account<'info, UnbondingAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,92 @@
+// in instructions/stakeproposal/change_vote.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::VoteChanged,
+    state::{Proposal, ProposalState, VoteChoice, VoteRecord},
+};
+
+/// 在投票截止前修改投票选项，权重保持不变；理事会计票中的权重一并转移。
+/// 投票中合并了委托权重时，需通过 remaining_accounts 传入全部委托人的投票记录，
+/// 委托人记录的投票选项随之修改
+pub fn change_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ChangeVote<'info>>,
+    new_choice: VoteChoice,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+
+    require!(
+        proposal.state == ProposalState::Voting && clock.unix_timestamp < proposal.end_time,
+        DaoError::ProposalNotActive
+    );
+    require!(vote_record.delegate.is_none(), DaoError::VoteCastByDelegate);
+
+    let old_choice = vote_record.choice;
+    proposal.remove_votes(old_choice, vote_record.weight)?;
+    proposal.add_votes(new_choice, vote_record.weight)?;
+    if vote_record.weight > 0 {
+        // 多选提案中投票人数和质押数量随选项一起转移
+        let voters = 1 + vote_record.delegator_count;
+        proposal.remove_voters(old_choice, voters, vote_record.raw_stake)?;
+        proposal.add_voters(new_choice, voters, vote_record.raw_stake)?;
+    }
+    if vote_record.council_weight > 0 {
+        proposal.council_votes.remove_votes(old_choice, vote_record.council_weight)?;
+        proposal.council_votes.add_votes(new_choice, vote_record.council_weight)?;
+    }
+    vote_record.choice = new_choice;
+
+    let proposal_key = proposal.key();
+    require!(
+        ctx.remaining_accounts.len() == vote_record.delegator_count as usize,
+        DaoError::MissingDelegatorVoteRecords
+    );
+    let mut delegators: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for record_info in ctx.remaining_accounts.iter() {
+        let mut delegator_record: Account<VoteRecord> = Account::try_from(record_info)?;
+        require_keys_eq!(delegator_record.proposal, proposal_key, DaoError::MissingDelegatorVoteRecords);
+        require!(
+            delegator_record.delegate == Some(vote_record.voter),
+            DaoError::MissingDelegatorVoteRecords
+        );
+        require!(!delegators.contains(&delegator_record.voter), DaoError::MissingDelegatorVoteRecords);
+        delegator_record.choice = new_choice;
+        delegator_record.exit(ctx.program_id)?;
+        delegators.push(delegator_record.voter);
+    }
+
+    emit!(VoteChanged {
+        proposal: proposal.key(),
+        voter: vote_record.voter,
+        old_choice,
+        new_choice,
+        weight: vote_record.weight,
+        council_weight: vote_record.council_weight,
+        new_yes_votes: proposal.yes_votes,
+        new_no_votes: proposal.no_votes,
+        new_abstain_votes: proposal.abstain_votes,
+        delegators,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ChangeVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        has_one = proposal,
+        has_one = voter
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
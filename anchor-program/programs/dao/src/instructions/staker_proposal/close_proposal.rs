@@ -0,0 +1,70 @@
+// in instructions/stakeproposal/close_proposal.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalClosed,
+    state::{DaoState, Proposal, ProposalMetadata},
+};
+
+/// 提案结束且超过 DAO 的保留时间后关闭提案账户，租金（及未退还的押金）返还给发起人。
+/// 任何人都可以调用
+pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let dao_state = &ctx.accounts.dao_state;
+    let clock = Clock::get()?;
+
+    let finalized_at = proposal.finalized_at.ok_or(DaoError::ProposalNotFinalized)?;
+    let closable_at = finalized_at
+        .checked_add(dao_state.proposal_retention_period)
+        .ok_or(DaoError::ArithmeticOverflow)?;
+    require!(clock.unix_timestamp >= closable_at, DaoError::RetentionPeriodNotOver);
+
+    emit!(ProposalClosed {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        title: ctx.accounts.proposal_metadata.title.clone(),
+        actions: proposal.actions.clone(),
+        state: proposal.state,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+        options: proposal.options.clone(),
+        council_votes: proposal.council_votes,
+        voter_count: proposal.voter_count,
+        executed_actions: proposal.executed_actions,
+        finalized_at,
+    });
+    // 关闭提案及元数据账户 (由 close = proposer 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump,
+        has_one = proposal,
+        close = proposer
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    /// CHECK: 提案发起人，仅接收返还的租金，由 has_one 约束校验
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
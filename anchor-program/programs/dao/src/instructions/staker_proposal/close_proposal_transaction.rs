@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalTransactionClosed,
+    state::{Proposal, ProposalTransaction},
+};
+
+/// 关闭提案的待执行指令账户，租金返还给发起人。
+/// 提案已结束，或已被取消、驳回、关闭时可以调用，任何人都可以调用
+pub fn close_proposal_transaction(ctx: Context<CloseProposalTransaction>) -> Result<()> {
+    let proposal_transaction = &ctx.accounts.proposal_transaction;
+    let proposal_info = &ctx.accounts.proposal;
+
+    // 提案账户已被关闭时无需再检查状态
+    if proposal_info.owner == ctx.program_id && !proposal_info.data_is_empty() {
+        let proposal = Proposal::try_deserialize(&mut &proposal_info.try_borrow_data()?[..])?;
+        require!(proposal.finalized_at.is_some(), DaoError::ProposalNotFinalized);
+    }
+
+    emit!(ProposalTransactionClosed {
+        proposal: proposal_transaction.proposal,
+        proposal_transaction: proposal_transaction.key(),
+        proposer: proposal_transaction.proposer,
+    });
+    // 关闭指令账户 (由 close = proposer 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseProposalTransaction<'info> {
+    /// CHECK: 指令账户所属的提案，可能已被关闭，存在时在指令中校验其状态
+    #[account(address = proposal_transaction.proposal)]
+    pub proposal: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal_transaction: Account<'info, ProposalTransaction>,
+
+    /// CHECK: 提案发起人，仅接收返还的租金，由 has_one 约束校验
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
@@ -0,0 +1,54 @@
+// in instructions/stakeproposal/close_vote_record.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::VoteRecordClosed,
+    state::{Proposal, VoteRecord},
+};
+
+/// 提案进入最终状态（执行、未通过、取消或过期）后关闭投票记录，租金返还给当初支付租金的账户：
+/// 亲自投票时为投票者，由受托人代投时为受托人。任何人都可以调用
+pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+    let vote_record = &ctx.accounts.vote_record;
+    let proposal_info = &ctx.accounts.proposal;
+
+    // 提案账户已被 close_proposal 关闭时无需再检查状态
+    if proposal_info.owner == ctx.program_id && !proposal_info.data_is_empty() {
+        let proposal = Proposal::try_deserialize(&mut &proposal_info.try_borrow_data()?[..])?;
+        require!(proposal.finalized_at.is_some(), DaoError::ProposalNotFinalized);
+    }
+
+    let rent_receiver = vote_record.delegate.unwrap_or(vote_record.voter);
+    require_keys_eq!(ctx.accounts.rent_receiver.key(), rent_receiver, DaoError::InvalidRentReceiver);
+
+    emit!(VoteRecordClosed {
+        proposal: vote_record.proposal,
+        voter: vote_record.voter,
+        choice: vote_record.choice,
+        weight: vote_record.weight,
+        delegate: vote_record.delegate,
+        rent_receiver,
+    });
+    // 关闭投票记录 (由 close = rent_receiver 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVoteRecord<'info> {
+    /// CHECK: 投票记录所属的提案，可能已被关闭，存在时在指令中校验其状态
+    #[account(address = vote_record.proposal)]
+    pub proposal: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_record", vote_record.proposal.as_ref(), vote_record.voter.as_ref()],
+        bump,
+        close = rent_receiver
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// CHECK: 接收返还租金的账户，在指令中校验
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
@@ -0,0 +1,157 @@
+// in instructions/stakeproposal/create_proposal.rs
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::DaoError,
+    event::StakeProposalCreated,
+    instructions::mul_proposal::{validate_proposal_actions, validate_proposal_options, validate_voter_population},
+    state::{DaoState, Proposal, ProposalMetadata, ProposalOption, ProposalState, ProposalType, QuorumMode, StakeAccount, VoteTally, VoterPopulation, VotingMode},
+};
+
+/// 质押者发起提案。发起人需质押不少于 min_staking_amount 的代币，
+/// 并缴纳 proposal_deposit 押金，押金托管在提案账户中。
+/// 提案同样需要多签批准才能进入投票，批准后押金可退还
+#[allow(clippy::too_many_arguments)]
+pub fn create_staker_proposal(
+    ctx: Context<CreateStakerProposal>,
+    actions: Vec<ProposalType>,
+    options: Vec<ProposalOption>,
+    voter_population: VoterPopulation,
+    title: String,
+    description: String,
+    metadata_uri: String,
+    content_hash: [u8; 32],
+) -> Result<()> {
+    let dao_state = &mut ctx.accounts.dao_state;
+    let stake_account = &ctx.accounts.stake_account;
+    let proposer = &ctx.accounts.proposer;
+    let clock = Clock::get()?;
+
+    // 1. 验证发起人的质押数量
+    require!(
+        stake_account.amount > 0 && stake_account.amount >= dao_state.min_staking_amount,
+        DaoError::InsufficientStake
+    );
+
+    // 2. 验证提案操作
+    validate_proposal_actions(dao_state, &actions)?;
+    validate_proposal_options(&actions, &options)?;
+    validate_voter_population(dao_state, voter_population, &options)?;
+    ProposalMetadata::validate(&title, &description, &metadata_uri)?;
+
+    // 3. 使用链上计数器分配提案 ID
+    let proposal_id = dao_state.proposal_count;
+    dao_state.proposal_count = proposal_id.checked_add(1).ok_or(DaoError::ArithmeticOverflow)?;
+
+    // 4. 将押金转入提案账户托管
+    let deposit_amount = dao_state.proposal_deposit;
+    if deposit_amount > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: proposer.to_account_info(),
+                    to: ctx.accounts.proposal.to_account_info(),
+                },
+            ),
+            deposit_amount,
+        )?;
+    }
+
+    ctx.accounts.proposal.set_inner(Proposal {
+        dao_state: dao_state.key(),
+        proposer: proposer.key(),
+        proposal_id,
+        actions: actions.clone(),
+        executed_actions: 0,
+        options: options.clone(),
+        voter_population,
+        approvals: Vec::new(),
+        rejections: Vec::new(),
+        spam_votes: Vec::new(),
+        yes_votes: 0,
+        no_votes: 0,
+        abstain_votes: 0,
+        voter_count: 0,
+        raw_votes: 0,
+        end_time: 0, // 多签批准后设置
+        state: ProposalState::Draft, // 发起人提交后进入审批
+        created_at: clock.unix_timestamp,
+        approved_at: None,
+        snapshot_total_staked: 0,
+        council_votes: VoteTally::default(),
+        snapshot_council_total_staked: 0,
+        quorum_mode: QuorumMode::default(), // 多签批准后设置
+        quorum: 0,
+        council_quorum: 0,
+        pass_threshold_percentage: 0,
+        voting_mode: VotingMode::default(),
+        council_voting_mode: VotingMode::default(),
+        executable_at: 0,
+        execution_deadline: 0,
+        deposit_amount,
+        finalized_at: None,
+    });
+
+    ctx.accounts.proposal_metadata.set_inner(ProposalMetadata {
+        proposal: ctx.accounts.proposal.key(),
+        title: title.clone(),
+        description: description.clone(),
+        metadata_uri: metadata_uri.clone(),
+        content_hash,
+    });
+
+    emit!(StakeProposalCreated {
+        dao_state: dao_state.key(),
+        proposal: ctx.accounts.proposal.key(),
+        proposal_id,
+        proposer: proposer.key(),
+        title,
+        description,
+        metadata_uri,
+        content_hash,
+        actions,
+        options,
+        voter_population,
+        deposit_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(actions: Vec<ProposalType>, options: Vec<ProposalOption>, voter_population: VoterPopulation, title: String, description: String, metadata_uri: String)]
+pub struct CreateStakerProposal<'info> {
+    #[account(mut)]
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &dao_state.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalMetadata::space(&title, &description, &metadata_uri),
+        seeds = [b"proposal_metadata".as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_metadata: Account<'info, ProposalMetadata>,
+
+    /// 发起人的质押账户，用于验证最低质押要求
+    #[account(
+        seeds = [b"stake_account", dao_state.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,109 +1,353 @@
 // in instructions/stakeproposal/execute_proposal.rs
-use anchor_lang::{prelude::*, system_program};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TransferChecked};
 
 use crate::{
     error::DaoError,
-    event::ProposalExecuted,
-    state::{DaoState, Proposal, ProposalType, RecurringPaymentAccount, DaoUpdateAction},
+    event::{DaoParametersUpdated, ProposalActionExecuted, ProposalExecuted, RewardsFunded},
+    state::{DaoState, DaoUpdateAction, Proposal, ProposalState, ProposalTransaction, ProposalType, RecurringPaymentAccount},
+    utils::{create_pda_account, transfer_into_vault},
 };
 
-pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+/// 执行已通过的提案。提案中的操作按顺序执行，每个操作需要的账户
+/// 依次通过 remaining_accounts 传入：
+/// - AddRecurringPayment: [定期支付 PDA]，账户尚不存在时需要传入 payer
+/// - WithdrawTreasury: [收款人]
+/// - UpdateDao: 无
+/// - ExecuteInstructions: [ProposalTransaction, 每条指令的 (程序, 指令账户...)]，只有国库 PDA 可以签名
+/// - FundRewards: [国库的治理代币账户, 治理代币 Mint, 奖励金库, 代币程序]
+///
+/// 多选提案只执行胜出选项对应的操作，胜出选项没有操作时直接标记为已执行。
+///
+/// `max_actions` 限制本次最多执行的操作数量，为空时执行全部剩余操作；
+/// 未执行完的提案保持 Succeeded 状态，可再次调用继续执行。
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    max_actions: Option<u8>,
+) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
     let dao_state = &mut ctx.accounts.dao_state;
     let clock = Clock::get()?;
 
     // 验证提案状态
-    require!(!proposal.executed, DaoError::ProposalAlreadyExecuted);
-    require!(proposal.approved_at.is_some(), DaoError::ProposalNotApproved);
-    require!(clock.unix_timestamp >= proposal.end_time, DaoError::ProposalNotActive);
-
-    // 计算投票结果
-    let total_votes = proposal.yes_votes + proposal.no_votes;
-    let pass_threshold = (total_votes * dao_state.pass_threshold_percentage as u64) / 100;
-    
-    // 检查是否达到法定人数
-    require!(total_votes >= dao_state.quorum as u64, DaoError::QuorumNotMet);
-    
-    // 检查是否通过
-    require!(proposal.yes_votes > pass_threshold, DaoError::ProposalNotPassed);
+    require!(proposal.state != ProposalState::Executed, DaoError::ProposalAlreadyExecuted);
+    require!(
+        !matches!(proposal.state, ProposalState::Draft | ProposalState::PendingApproval),
+        DaoError::ProposalNotApproved
+    );
+
+    // 投票期结束但尚未 finalize 的提案，在此直接计票
+    if proposal.state == ProposalState::Voting {
+        require!(clock.unix_timestamp >= proposal.end_time, DaoError::VotePeriodNotOver);
+        // 检查是否达到法定人数
+        require!(proposal.quorum_reached(), DaoError::QuorumNotMet);
+        // 检查是否通过
+        require!(proposal.vote_passed(), DaoError::ProposalNotPassed);
+        proposal.mark_succeeded(dao_state)?;
+    }
+    require!(proposal.state == ProposalState::Succeeded, DaoError::InvalidProposalState);
+
+    // 检查时间锁与执行窗口
+    require!(clock.unix_timestamp >= proposal.executable_at, DaoError::ProposalTimelocked);
+    require!(clock.unix_timestamp <= proposal.execution_deadline, DaoError::ProposalExpired);
+
+    let plan = proposal.execution_plan();
+    let start = proposal.executed_actions as usize;
+    let end = match max_actions {
+        Some(max) => start.saturating_add(max as usize).min(plan.len()),
+        None => plan.len(),
+    };
+    require!(end > start || plan.is_empty(), DaoError::InvalidActionCount);
+
+    let dao_key = dao_state.key();
+    let treasury_bump = ctx.bumps.treasury;
+    let seeds = &[
+        b"treasury".as_ref(),
+        dao_key.as_ref(),
+        &[treasury_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
 
     // 执行提案
-    match &proposal.proposal_type {
-        // 执行定期支付
-        ProposalType::AddRecurringPayment { recipient, amount, currency, interval } => {
-            require_keys_eq!(ctx.accounts.recipient.key(), *recipient, DaoError::InvalidRecipient);
-            let recurring_payment = &mut ctx.accounts.recurring_payment;
-            let now = clock.unix_timestamp;
-            recurring_payment.dao_state = dao_state.key();
-            recurring_payment.receiver = *recipient;
-            recurring_payment.amount = *amount;
-            recurring_payment.currency = *currency;
-            recurring_payment.interval_day = *interval;
-            recurring_payment.next_claimable_timestamp = now.checked_add(*interval)
-                .ok_or(DaoError::ArithmeticOverflow)?;
-        },
-        // 执行更新DAO
-        ProposalType::UpdateDao { action } => {
-            match action {
-                DaoUpdateAction::AddSigner { new_signer } => {
-                    require!(!dao_state.signer.contains(new_signer), DaoError::SignerAlreadyExists);
-                    dao_state.signer.push(*new_signer);
-                },
-                DaoUpdateAction::RemoveSigner { signer_to_remove } => {
-                    require!(dao_state.signer.contains(signer_to_remove), DaoError::SignerNotFound);
-                    require!((dao_state.signer.len() - 1) as u8 >= dao_state.threshold, DaoError::CannotRemoveSigner);
-                    dao_state.signer.retain(|s| s != signer_to_remove);
-                },
-                DaoUpdateAction::ChangeThreshold { new_threshold } => {
-                    require!(*new_threshold > 0 && *new_threshold <= dao_state.signer.len() as u8, DaoError::InvalidNewThreshold);
-                    dao_state.threshold = *new_threshold;
-                },
+    let mut remaining_accounts = ctx.remaining_accounts;
+    for &index in &plan[start..end] {
+        let action = proposal.actions[index];
+        match &action {
+            // 执行定期支付
+            ProposalType::AddRecurringPayment { recipient, amount, currency, interval } => {
+                let payment_info = &next_accounts(&mut remaining_accounts, 1)?[0];
+                let (payment_key, payment_bump) = Pubkey::find_program_address(
+                    &[b"payment", dao_key.as_ref(), recipient.as_ref()],
+                    ctx.program_id,
+                );
+                require_keys_eq!(payment_info.key(), payment_key, DaoError::InvalidActionAccount);
+
+                // 不存在则创建；已存在时覆盖为新的支付条款
+                if payment_info.owner != ctx.program_id {
+                    create_pda_account(
+                        ctx.accounts.payer.as_ref().ok_or(DaoError::MissingPayer)?.to_account_info(),
+                        payment_info.clone(),
+                        ctx.accounts.system_program.to_account_info(),
+                        8 + RecurringPaymentAccount::INIT_SPACE,
+                        &[b"payment", dao_key.as_ref(), recipient.as_ref(), &[payment_bump]],
+                    )?;
+                }
+                let recurring_payment = RecurringPaymentAccount {
+                    dao_state: dao_key,
+                    receiver: *recipient,
+                    amount: *amount,
+                    currency: *currency,
+                    interval_day: *interval,
+                    next_claimable_timestamp: clock.unix_timestamp.checked_add(*interval)
+                        .ok_or(DaoError::ArithmeticOverflow)?,
+                };
+                recurring_payment.try_serialize(&mut &mut payment_info.try_borrow_mut_data()?[..])?;
+            },
+            // 执行更新DAO
+            ProposalType::UpdateDao { action } => {
+                let old_parameters = dao_state.parameters();
+                apply_dao_update(dao_state, action)?;
+                let new_parameters = dao_state.parameters();
+                if new_parameters != old_parameters {
+                    emit!(DaoParametersUpdated {
+                        dao_state: dao_key,
+                        proposal: proposal.key(),
+                        old_parameters,
+                        new_parameters,
+                    });
+                }
+            },
+            // 执行国库提款
+            ProposalType::WithdrawTreasury { amount, recipient } => {
+                let recipient_info = &next_accounts(&mut remaining_accounts, 1)?[0];
+                require_keys_eq!(recipient_info.key(), *recipient, DaoError::InvalidRecipient);
+                let treasury = &ctx.accounts.treasury;
+                require!(treasury.lamports() >= *amount, DaoError::InsufficientTreasuryBalance);
+
+                let cpi_context = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: treasury.to_account_info(),
+                        to: recipient_info.clone(),
+                    },
+                );
+                system_program::transfer(cpi_context.with_signer(signer_seeds), *amount)?;
+            },
+            // 以国库 PDA 签名依次执行操作中的指令
+            ProposalType::ExecuteInstructions { instruction_count } => {
+                let transaction_info = &next_accounts(&mut remaining_accounts, 1)?[0];
+                let (expected_transaction, _) = Pubkey::find_program_address(
+                    &[b"proposal_transaction", proposal.key().as_ref(), &[index as u8]],
+                    ctx.program_id,
+                );
+                require_keys_eq!(transaction_info.key(), expected_transaction, DaoError::InvalidProposalTransaction);
+                let proposal_transaction: Account<ProposalTransaction> = Account::try_from(transaction_info)?;
+                require!(
+                    proposal_transaction.instructions.len() == *instruction_count as usize,
+                    DaoError::InvalidInstructionCount
+                );
+
+                for ix in proposal_transaction.instructions.iter() {
+                    require!(
+                        ix.only_signed_by(&ctx.accounts.treasury.key()),
+                        DaoError::InvalidInstructionSigner
+                    );
+                    let ix_accounts = next_accounts(&mut remaining_accounts, 1 + ix.accounts.len())?;
+                    let mut account_infos = Vec::with_capacity(ix_accounts.len() + 1);
+                    account_infos.push(ctx.accounts.treasury.to_account_info());
+                    account_infos.extend_from_slice(ix_accounts);
+
+                    let instruction: Instruction = ix.into();
+                    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+                }
+            },
+            // 以国库 PDA 签名向奖励金库注资，按实际到账数量计入待分配奖励
+            ProposalType::FundRewards { amount } => {
+                let accounts = next_accounts(&mut remaining_accounts, 4)?;
+                let (source_info, mint_info, vault_info, token_program_info) =
+                    (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+                let (reward_vault_key, _) = Pubkey::find_program_address(
+                    &[b"reward_vault", dao_key.as_ref()],
+                    ctx.program_id,
+                );
+                require_keys_eq!(vault_info.key(), reward_vault_key, DaoError::InvalidActionAccount);
+                require_keys_eq!(mint_info.key(), dao_state.token_mint, DaoError::InvalidActionAccount);
+                require_keys_eq!(token_program_info.key(), *mint_info.owner, DaoError::InvalidActionAccount);
+                let mint: InterfaceAccount<Mint> = InterfaceAccount::try_from(mint_info)?;
+                let mut reward_vault: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(vault_info)?;
+
+                // 先按注资前的余额累计奖励
+                dao_state.update_rewards(clock.unix_timestamp)?;
+                let cpi_accounts = TransferChecked {
+                    from: source_info.clone(),
+                    mint: mint_info.clone(),
+                    to: vault_info.clone(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                };
+                let funded = transfer_into_vault(
+                    CpiContext::new_with_signer(token_program_info.clone(), cpi_accounts, signer_seeds),
+                    &mut reward_vault,
+                    *amount,
+                    mint.decimals,
+                )?;
+                require!(funded > 0, DaoError::InvalidRewardAmount);
+                dao_state.undistributed_rewards = dao_state
+                    .undistributed_rewards
+                    .checked_add(funded)
+                    .ok_or(DaoError::ArithmeticOverflow)?;
+
+                emit!(RewardsFunded {
+                    dao_state: dao_key,
+                    funder: ctx.accounts.treasury.key(),
+                    amount: funded,
+                    undistributed_rewards: dao_state.undistributed_rewards,
+                });
+            },
+        }
+
+        proposal.executed_actions += 1;
+        emit!(ProposalActionExecuted {
+            dao_state: dao_key,
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            action_index: index as u8,
+            action,
+        });
+    }
+
+    // 全部操作执行完毕
+    if proposal.executed_actions as usize == plan.len() {
+        proposal.state = ProposalState::Executed;
+        proposal.finalized_at = Some(clock.unix_timestamp);
+
+        // 触发"执行"事件
+        emit!(ProposalExecuted {
+            dao_state: dao_key,
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            actions: plan.iter().map(|&index| proposal.actions[index]).collect(),
+            executable_at: proposal.executable_at,
+            execution_deadline: proposal.execution_deadline,
+        });
+    }
+
+    Ok(())
+}
+
+/// 应用一项 DAO 设置修改
+fn apply_dao_update(dao_state: &mut DaoState, action: &DaoUpdateAction) -> Result<()> {
+    match action {
+        DaoUpdateAction::AddSigner { new_signer } => {
+            require!(!dao_state.signer.contains(new_signer), DaoError::SignerAlreadyExists);
+            dao_state.signer.push(*new_signer);
+        },
+        DaoUpdateAction::RemoveSigner { signer_to_remove } => {
+            require!(dao_state.signer.contains(signer_to_remove), DaoError::SignerNotFound);
+            require!((dao_state.signer.len() - 1) as u8 >= dao_state.threshold, DaoError::CannotRemoveSigner);
+            dao_state.signer.retain(|s| s != signer_to_remove);
+        },
+        DaoUpdateAction::ChangeThreshold { new_threshold } => {
+            require!(*new_threshold > 0 && *new_threshold <= dao_state.signer.len() as u8, DaoError::InvalidNewThreshold);
+            dao_state.threshold = *new_threshold;
+        },
+        DaoUpdateAction::ChangeTimelock { hold_up_time, execution_window } => {
+            require!(*hold_up_time >= 0, DaoError::InvalidHoldUpTime);
+            require!(*execution_window > 0, DaoError::InvalidExecutionWindow);
+            dao_state.hold_up_time = *hold_up_time;
+            dao_state.execution_window = *execution_window;
+        },
+        DaoUpdateAction::AppointGuardian { guardian } => {
+            require!(*guardian != Pubkey::default(), DaoError::InvalidGuardian);
+            dao_state.guardian = Some(*guardian);
+        },
+        DaoUpdateAction::RemoveGuardian => {
+            require!(dao_state.guardian.is_some(), DaoError::GuardianNotSet);
+            dao_state.guardian = None;
+        },
+        DaoUpdateAction::ChangeProposalDeposit { proposal_deposit } => {
+            dao_state.proposal_deposit = *proposal_deposit;
+        },
+        DaoUpdateAction::ChangeQuorum { quorum_mode, quorum } => {
+            quorum_mode.validate(*quorum)?;
+            for rule in dao_state.governance_rules.iter() {
+                quorum_mode.validate(rule.quorum)?;
+            }
+            if dao_state.council_mint.is_some() {
+                quorum_mode.validate(dao_state.council_quorum)?;
             }
+            dao_state.quorum_mode = *quorum_mode;
+            dao_state.quorum = *quorum;
         },
-        // 执行国库提款
-        ProposalType::WithdrawTreasury { amount, recipient } => {
-            require_keys_eq!(ctx.accounts.recipient.key(), *recipient, DaoError::InvalidRecipient);
-            let treasury = &ctx.accounts.treasury;
-            let system_program = &ctx.accounts.system_program;
-            require!(treasury.lamports() >= *amount, DaoError::InsufficientTreasuryBalance);
-            
-            let cpi_context = CpiContext::new(
-                system_program.to_account_info(),
-                system_program::Transfer {
-                    from: treasury.to_account_info(),
-                    to: ctx.accounts.recipient.to_account_info(),
-                },
+        DaoUpdateAction::ChangeVoteDuration { vote_duration } => {
+            require!(*vote_duration > 0, DaoError::InvalidVoteDuration);
+            dao_state.vote_duration = *vote_duration;
+        },
+        DaoUpdateAction::ChangeStakingYieldRate { staking_yield_rate } => {
+            // 先按旧收益率累计到当前时间
+            dao_state.update_rewards(Clock::get()?.unix_timestamp)?;
+            dao_state.staking_yield_rate = *staking_yield_rate;
+        },
+        DaoUpdateAction::ChangePassThreshold { pass_threshold_percentage } => {
+            require!(
+                *pass_threshold_percentage > 0 && *pass_threshold_percentage <= 100,
+                DaoError::InvalidPassThreshold
             );
-            
-            let dao_key = dao_state.key();
-            let treasury_bump = ctx.bumps.treasury;
-            let seeds = &[
-                b"treasury".as_ref(),
-                dao_key.as_ref(),
-                &[treasury_bump],
-            ];
-            let signer_seeds = &[&seeds[..]];
-
-            system_program::transfer(cpi_context.with_signer(signer_seeds), *amount)?;
+            dao_state.pass_threshold_percentage = *pass_threshold_percentage;
+        },
+        DaoUpdateAction::ChangeMinStakingAmount { min_staking_amount } => {
+            dao_state.min_staking_amount = *min_staking_amount;
+        },
+        DaoUpdateAction::ChangeVotingMode { voting_mode } => {
+            dao_state.voting_mode = *voting_mode;
+        },
+        DaoUpdateAction::ChangeCouncilVotingMode { council_voting_mode } => {
+            require!(dao_state.council_mint.is_some(), DaoError::CouncilNotConfigured);
+            dao_state.council_voting_mode = *council_voting_mode;
+        },
+        DaoUpdateAction::ChangeCouncilQuorum { council_quorum } => {
+            require!(dao_state.council_mint.is_some(), DaoError::CouncilNotConfigured);
+            dao_state.quorum_mode.validate(*council_quorum)?;
+            dao_state.council_quorum = *council_quorum;
+        },
+        DaoUpdateAction::ChangeProposalRetention { proposal_retention_period } => {
+            require!(*proposal_retention_period >= 0, DaoError::InvalidRetentionPeriod);
+            dao_state.proposal_retention_period = *proposal_retention_period;
+        },
+        DaoUpdateAction::ChangeUnbondingPeriod { unbonding_period } => {
+            require!(*unbonding_period >= 0, DaoError::InvalidUnbondingPeriod);
+            dao_state.unbonding_period = *unbonding_period;
+        },
+        DaoUpdateAction::SetGovernanceRule { rule } => {
+            dao_state.quorum_mode.validate(rule.quorum)?;
+            dao_state.governance_rules.retain(|r| r.kind != rule.kind);
+            dao_state.governance_rules.push(*rule);
+        },
+        DaoUpdateAction::RemoveGovernanceRule { kind } => {
+            require!(dao_state.governance_rules.iter().any(|r| r.kind == *kind), DaoError::GovernanceRuleNotFound);
+            dao_state.governance_rules.retain(|r| r.kind != *kind);
         },
     }
-    
-    proposal.executed = true;
-
-    // 触发"执行"事件
-    emit!(ProposalExecuted {
-        dao_state: dao_state.key(),
-        proposal: proposal.key(),
-        proposal_id: proposal.proposal_id,
-        proposal_type: proposal.proposal_type.clone(),
-    });
-
     Ok(())
 }
 
+/// 从 remaining_accounts 头部取出 n 个账户
+fn next_accounts<'a, 'info>(
+    remaining_accounts: &mut &'a [AccountInfo<'info>],
+    n: usize,
+) -> Result<&'a [AccountInfo<'info>]> {
+    require!(remaining_accounts.len() >= n, DaoError::MissingActionAccounts);
+    let (taken, rest) = remaining_accounts.split_at(n);
+    *remaining_accounts = rest;
+    Ok(taken)
+}
+
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
-    #[account(mut, has_one = authority)]
+    #[account(mut)]
     pub dao_state: Account<'info, DaoState>,
 
     #[account(
@@ -114,9 +358,10 @@ pub struct ExecuteProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
-    /// CHECK: DAO 的 authority，用于 has_one 约束
+    /// 执行者，支付执行过程中新建账户（如定期支付账户）的租金；
+    /// 不需要新建账户时可以不传，避免其签名出现在执行上下文中
     #[account(mut)]
-    pub authority: AccountInfo<'info>,
+    pub payer: Option<Signer<'info>>,
 
     #[account(
         mut,
@@ -124,19 +369,6 @@ pub struct ExecuteProposal<'info> {
         bump
     )]
     pub treasury: SystemAccount<'info>,
-    
-    #[account(mut)]
-    /// CHECK: The recipient account, verified in the instruction logic.
-    pub recipient: UncheckedAccount<'info>,
-
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = 8 + RecurringPaymentAccount::INIT_SPACE,
-        seeds = [b"payment".as_ref(), dao_state.key().as_ref(), recipient.key().as_ref()],
-        bump
-    )]
-    pub recurring_payment: Account<'info, RecurringPaymentAccount>,
 
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,62 @@
+// in instructions/stakeproposal/finalize_proposal.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalFinalized,
+    state::{DaoState, Proposal, ProposalState},
+};
+
+/// 投票期结束后计票并记录结果；已通过但错过执行窗口的提案标记为过期。任何人都可以调用
+pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let dao_state = &ctx.accounts.dao_state;
+    let clock = Clock::get()?;
+
+    match proposal.state {
+        ProposalState::Voting => {
+            require!(clock.unix_timestamp >= proposal.end_time, DaoError::VotePeriodNotOver);
+            if proposal.tally() == ProposalState::Succeeded {
+                proposal.mark_succeeded(dao_state)?;
+            } else {
+                proposal.state = ProposalState::Defeated;
+                proposal.finalized_at = Some(clock.unix_timestamp);
+            }
+        }
+        ProposalState::Succeeded => {
+            require!(clock.unix_timestamp > proposal.execution_deadline, DaoError::InvalidProposalState);
+            proposal.state = ProposalState::Expired;
+            proposal.finalized_at = Some(clock.unix_timestamp);
+        }
+        _ => return err!(DaoError::InvalidProposalState),
+    }
+
+    emit!(ProposalFinalized {
+        dao_state: dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        state: proposal.state,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        abstain_votes: proposal.abstain_votes,
+        options: proposal.options.clone(),
+        winning_option: proposal.winning_option(),
+        council_votes: proposal.council_votes,
+        voter_count: proposal.voter_count,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
@@ -1,7 +1,25 @@
 
+pub mod create_proposal;
+pub mod refund_proposal_deposit;
 pub mod vote;
 pub mod execute_proposal;
+pub mod finalize_proposal;
+pub mod change_vote;
+pub mod relinquish_vote;
+pub mod veto_proposal;
+pub mod close_vote_record;
+pub mod close_proposal;
+pub mod close_proposal_transaction;
 
 
+pub use create_proposal::*;
+pub use refund_proposal_deposit::*;
 pub use vote::*;
-pub use execute_proposal::*;
\ No newline at end of file
+pub use execute_proposal::*;
+pub use finalize_proposal::*;
+pub use change_vote::*;
+pub use relinquish_vote::*;
+pub use veto_proposal::*;
+pub use close_vote_record::*;
+pub use close_proposal::*;
+pub use close_proposal_transaction::*;
\ No newline at end of file
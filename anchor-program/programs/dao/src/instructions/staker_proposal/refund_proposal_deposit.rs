@@ -0,0 +1,51 @@
+// in instructions/stakeproposal/refund_proposal_deposit.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalDepositRefunded,
+    state::Proposal,
+    utils::transfer_program_lamports,
+};
+
+/// 提案经多签批准进入投票后，将押金退还给发起人（无需权限）
+pub fn refund_proposal_deposit(ctx: Context<RefundProposalDeposit>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(proposal.deposit_amount > 0, DaoError::NoProposalDeposit);
+    // 只要提案进入过投票阶段，无论结果如何都退还押金
+    require!(proposal.approved_at.is_some(), DaoError::DepositNotRefundable);
+
+    let amount = proposal.deposit_amount;
+    proposal.deposit_amount = 0;
+    transfer_program_lamports(
+        &proposal.to_account_info(),
+        &ctx.accounts.proposer.to_account_info(),
+        amount,
+    )?;
+
+    emit!(ProposalDepositRefunded {
+        dao_state: proposal.dao_state,
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundProposalDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), proposal.dao_state.as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: 提案发起人，仅接收退还的押金，由 has_one 约束校验
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
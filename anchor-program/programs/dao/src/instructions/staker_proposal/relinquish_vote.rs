@@ -0,0 +1,66 @@
+// in instructions/stakeproposal/relinquish_vote.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::VoteRelinquished,
+    state::{Proposal, ProposalState, VoteRecord},
+};
+
+/// 在投票截止前撤回投票，并关闭投票记录返还租金
+pub fn relinquish_vote(ctx: Context<RelinquishVote>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+
+    require!(
+        proposal.state == ProposalState::Voting && clock.unix_timestamp < proposal.end_time,
+        DaoError::ProposalNotActive
+    );
+    require!(vote_record.delegate.is_none(), DaoError::VoteCastByDelegate);
+    // 委托人的投票记录已被占用，撤回会使其失去本次投票权
+    require!(vote_record.delegator_count == 0, DaoError::CannotRelinquishDelegatedVote);
+
+    // 与投票时一致：只在计入了权重的计票中扣减投票人数
+    if vote_record.weight > 0 {
+        proposal.remove_votes(vote_record.choice, vote_record.weight)?;
+        proposal.remove_voters(vote_record.choice, 1, vote_record.raw_stake)?;
+    }
+    if vote_record.council_weight > 0 {
+        proposal.council_votes.remove_votes(vote_record.choice, vote_record.council_weight)?;
+        proposal.council_votes.remove_voter(vote_record.council_raw_stake)?;
+    }
+
+    emit!(VoteRelinquished {
+        proposal: proposal.key(),
+        voter: vote_record.voter,
+        choice: vote_record.choice,
+        weight: vote_record.weight,
+        council_weight: vote_record.council_weight,
+        new_yes_votes: proposal.yes_votes,
+        new_no_votes: proposal.no_votes,
+        new_abstain_votes: proposal.abstain_votes,
+        new_voter_count: proposal.voter_count,
+    });
+    // 关闭投票记录，返还租金 (由 close = voter 宏自动处理)
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RelinquishVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_record", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        has_one = proposal,
+        has_one = voter,
+        close = voter
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
@@ -0,0 +1,57 @@
+// in instructions/stakeproposal/veto_proposal.rs
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DaoError,
+    event::ProposalVetoed,
+    state::{DaoState, Proposal, ProposalState, MAX_VETO_REASON_LEN},
+};
+
+/// 守护者在时间锁期间否决一个已通过但尚未执行的提案。
+/// 投票期已结束但尚未 finalize 的提案先在此计票，通过后再否决
+pub fn veto_proposal(ctx: Context<VetoProposal>, reason: String) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(reason.len() <= MAX_VETO_REASON_LEN, DaoError::VetoReasonTooLong);
+    if proposal.state == ProposalState::Voting {
+        require!(Clock::get()?.unix_timestamp >= proposal.end_time, DaoError::VotePeriodNotOver);
+        require!(proposal.tally() == ProposalState::Succeeded, DaoError::ProposalNotPassed);
+        proposal.mark_succeeded(&ctx.accounts.dao_state)?;
+    }
+    // 只能否决已计票通过、且一个操作都还没执行的提案
+    require!(
+        proposal.state == ProposalState::Succeeded && proposal.executed_actions == 0,
+        DaoError::InvalidProposalState
+    );
+
+    proposal.state = ProposalState::Cancelled;
+    proposal.finalized_at = Some(Clock::get()?.unix_timestamp);
+
+    emit!(ProposalVetoed {
+        dao_state: ctx.accounts.dao_state.key(),
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        guardian: ctx.accounts.guardian.key(),
+        reason,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    pub dao_state: Account<'info, DaoState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), dao_state.key().as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump,
+        has_one = dao_state
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        constraint = dao_state.guardian == Some(guardian.key()) @ DaoError::UnauthorizedGuardian
+    )]
+    pub guardian: Signer<'info>,
+}
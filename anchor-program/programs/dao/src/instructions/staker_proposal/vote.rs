@@ -1,76 +1,188 @@
 // in instructions/stakeproposal/vote.rs
 use anchor_lang::prelude::*;
-use crate::{error::DaoError, event::VoteCasted, state::{DaoState, Proposal, StakeAccount, VoteRecord}};
+use crate::{error::DaoError, event::VoteCasted, utils::create_pda_account, state::{DaoState, Delegation, GoverningToken, Proposal, ProposalState, StakeAccount, VoteChoice, VoteRecord, VoterPopulation}};
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub enum VoteChoice {
-    Yes,
-    No,
-}
-
-pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
+/// 投票。投票者可通过 remaining_accounts 按 (委托账户, 委托人质押账户, 委托人投票记录)
+/// 三元组传入委托给自己的投票权，合并计入本次投票。委托只适用于社区代币。
+///
+/// 按提案的投票人群使用社区或理事会质押账户；两者共同投票时，
+/// 同一次投票分别计入社区和理事会的计票
+pub fn vote<'info>(ctx: Context<'_, '_, 'info, 'info, Vote<'info>>, choice: VoteChoice) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
-    let stake_account = &ctx.accounts.stake_account;
+    let dao_state = &ctx.accounts.dao_state;
+    let voter = &ctx.accounts.voter;
     let clock = Clock::get()?;
 
-    // 验证提案是否处于可投票状态
-    require!(!proposal.executed, DaoError::ProposalAlreadyExecuted);
-    
     // 验证提案是否已经通过多签批准
     require!(
-        proposal.approved_at.is_some(), 
+        !matches!(proposal.state, ProposalState::Draft | ProposalState::PendingApproval),
         DaoError::ProposalNotApproved
     );
-    
-    // 验证投票是否在有效期内
+
+    // 验证提案处于投票阶段且投票在有效期内
     require!(
-        clock.unix_timestamp < proposal.end_time, 
+        proposal.state == ProposalState::Voting && clock.unix_timestamp < proposal.end_time,
         DaoError::ProposalNotActive
     );
-    
-    // 验证投票者是否已质押足够数量
-    let dao_state = &ctx.accounts.dao_state;
-    require!(
-        stake_account.amount >= dao_state.min_staking_amount, 
-        DaoError::InsufficientStake
-    );
 
     // 验证是否已经投票过
     require!(
-        !ctx.accounts.vote_record.proposal.eq(&proposal.key()) || 
+        !ctx.accounts.vote_record.proposal.eq(&proposal.key()) ||
         ctx.accounts.vote_record.proposal.eq(&Pubkey::default()),
         DaoError::AlreadyVoted
     );
 
-    // 更新票数
-    let vote_weight = stake_account.amount;
-    match choice {
-        VoteChoice::Yes => {
-            proposal.yes_votes = proposal.yes_votes.checked_add(vote_weight)
-                .ok_or(DaoError::ArithmeticOverflow)?;
-        },
-        VoteChoice::No => {
-            proposal.no_votes = proposal.no_votes.checked_add(vote_weight)
-                .ok_or(DaoError::ArithmeticOverflow)?;
-        },
+    // 投票权重取快照时刻（多签批准时）的质押数量，投票期间新增的质押不计入；
+    // 再乘以投票时剩余锁仓时间对应的倍数
+    let snapshot = proposal.approved_at.ok_or(DaoError::ProposalNotApproved)?;
+    let population = proposal.voter_population;
+
+    // 1. 投票者自己的社区代币质押权重，按批准时锁定的投票模式换算
+    let mut vote_weight = 0u64;
+    let mut raw_stake = 0u64;
+    let stake_account = ctx.accounts.stake_account.as_mut()
+        .filter(|_| population.includes(GoverningToken::Community));
+    if let Some(stake_account) = stake_account {
+        raw_stake = stake_account.amount_at(snapshot)?;
+        vote_weight = proposal
+            .voting_mode
+            .apply(stake_account.voting_weight(snapshot, clock.unix_timestamp)?);
+        // 锁定质押直到该提案投票结束，防止赎回后换钱包重复投票
+        stake_account.locked_until = stake_account.locked_until.max(proposal.end_time);
+    }
+
+    // 2. 合并委托给投票者的权重。已亲自投过票的委托人会被跳过
+    let proposal_key = proposal.key();
+    let mut delegators = Vec::new();
+    let delegated_accounts = ctx.remaining_accounts.chunks_exact(3);
+    require!(delegated_accounts.remainder().is_empty(), DaoError::InvalidDelegationAccounts);
+    require!(
+        ctx.remaining_accounts.is_empty() || population.includes(GoverningToken::Community),
+        DaoError::InvalidDelegationAccounts
+    );
+    for accounts in delegated_accounts {
+        let (delegation_info, stake_info, record_info) = (&accounts[0], &accounts[1], &accounts[2]);
+
+        let delegation: Account<Delegation> = Account::try_from(delegation_info)?;
+        require_keys_eq!(delegation.delegate, voter.key(), DaoError::InvalidDelegation);
+        require_keys_eq!(delegation.dao_state, dao_state.key(), DaoError::InvalidDelegation);
+
+        let mut delegator_stake: Account<StakeAccount> = Account::try_from(stake_info)?;
+        require_keys_eq!(delegator_stake.staker, delegation.delegator, DaoError::InvalidDelegation);
+        require_keys_eq!(delegator_stake.dao_state, dao_state.key(), DaoError::InvalidDelegation);
+        require!(
+            delegator_stake.governing_token == GoverningToken::Community,
+            DaoError::InvalidDelegation
+        );
+
+        let (record_key, record_bump) = Pubkey::find_program_address(
+            &[b"vote_record", proposal_key.as_ref(), delegation.delegator.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(record_info.key(), record_key, DaoError::InvalidDelegationAccounts);
+
+        // 委托人已经亲自投票（或已被使用），跳过
+        if record_info.owner == ctx.program_id {
+            continue;
+        }
+        let delegator_raw_stake = delegator_stake.amount_at(snapshot)?;
+        let weight = proposal
+            .voting_mode
+            .apply(delegator_stake.voting_weight(snapshot, clock.unix_timestamp)?);
+        if weight == 0 {
+            continue;
+        }
+
+        // 为委托人创建投票记录，阻止其之后再重复投票
+        create_pda_account(
+            voter.to_account_info(),
+            record_info.clone(),
+            ctx.accounts.system_program.to_account_info(),
+            8 + VoteRecord::INIT_SPACE,
+            &[b"vote_record", proposal_key.as_ref(), delegation.delegator.as_ref(), &[record_bump]],
+        )?;
+        let record = VoteRecord {
+            proposal: proposal_key,
+            voter: delegation.delegator,
+            weight,
+            raw_stake: delegator_raw_stake,
+            council_weight: 0,
+            council_raw_stake: 0,
+            choice,
+            delegate: Some(voter.key()),
+            delegator_count: 0,
+        };
+        record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        delegator_stake.locked_until = delegator_stake.locked_until.max(proposal.end_time);
+        delegator_stake.exit(ctx.program_id)?;
+
+        vote_weight = vote_weight.checked_add(weight).ok_or(DaoError::ArithmeticOverflow)?;
+        raw_stake = raw_stake.checked_add(delegator_raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        delegators.push(delegation.delegator);
+    }
+
+    // 3. 投票者的理事会代币质押权重，按批准时锁定的理事会投票模式换算
+    let mut council_weight = 0u64;
+    let mut council_raw_stake = 0u64;
+    let council_stake = ctx.accounts.council_stake_account.as_mut()
+        .filter(|_| population.includes(GoverningToken::Council));
+    if let Some(council_stake) = council_stake {
+        council_raw_stake = council_stake.amount_at(snapshot)?;
+        council_weight = proposal
+            .council_voting_mode
+            .apply(council_stake.voting_weight(snapshot, clock.unix_timestamp)?);
+        council_stake.locked_until = council_stake.locked_until.max(proposal.end_time);
+    }
+
+    // 验证投票者在快照时是否已质押；社区代币的最低质押按换算前的数量（含委托）判断
+    require!(
+        vote_weight == 0 || raw_stake >= dao_state.min_staking_amount,
+        DaoError::InsufficientStake
+    );
+    require!(vote_weight > 0 || council_weight > 0, DaoError::InsufficientStake);
+
+    // 仅理事会投票时，理事会权重计入主计票
+    let (vote_weight, raw_stake, council_weight, council_raw_stake) = match population {
+        VoterPopulation::Council => (council_weight, council_raw_stake, 0, 0),
+        _ => (vote_weight, raw_stake, council_weight, council_raw_stake),
+    };
+
+    // 更新票数，投票人数只计入有权重的计票
+    if vote_weight > 0 {
+        proposal.add_votes(choice, vote_weight)?;
+        proposal.add_voters(choice, 1 + delegators.len() as u32, raw_stake)?;
+    }
+    if council_weight > 0 {
+        proposal.council_votes.add_votes(choice, council_weight)?;
+        proposal.council_votes.add_voter(council_raw_stake)?;
     }
-    proposal.voter_count = proposal.voter_count.checked_add(1)
-        .ok_or(DaoError::ArithmeticOverflow)?;
 
     // 记录投票，防止重复投票
     let vote_record = &mut ctx.accounts.vote_record;
     vote_record.proposal = proposal.key();
-    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.voter = voter.key();
     vote_record.weight = vote_weight;
+    vote_record.raw_stake = raw_stake;
+    vote_record.council_weight = council_weight;
+    vote_record.council_raw_stake = council_raw_stake;
+    vote_record.choice = choice;
+    vote_record.delegate = None;
+    vote_record.delegator_count = delegators.len() as u32;
 
     emit!(VoteCasted {
         proposal: proposal.key(),
         voter: vote_record.voter,
         choice,
         weight: vote_record.weight,
+        raw_stake,
+        council_weight,
         new_yes_votes: proposal.yes_votes,
         new_no_votes: proposal.no_votes,
+        new_abstain_votes: proposal.abstain_votes,
         new_voter_count: proposal.voter_count,
+        delegators,
+        council_votes: proposal.council_votes,
     });
 
     Ok(())
@@ -81,12 +193,22 @@ pub struct Vote<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
 
-    /// 获取投票权重
+    /// 获取投票权重，并记录质押锁定时间。
+    /// 仅代表他人投票、自己没有质押的受托人可以不传
     #[account(
+        mut,
         seeds = [b"stake_account", proposal.dao_state.as_ref(), voter.key().as_ref()],
         bump
     )]
-    pub stake_account: Account<'info, StakeAccount>,
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
+    /// 理事会代币的质押账户，理事会参与投票的提案使用
+    #[account(
+        mut,
+        seeds = [b"council_stake_account", proposal.dao_state.as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub council_stake_account: Option<Account<'info, StakeAccount>>,
 
     #[account(
         constraint = dao_state.key() == proposal.dao_state
@@ -107,4 +229,4 @@ pub dao_state: Account<'info, DaoState>,
     pub vote_record: Account<'info, VoteRecord>,
 
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
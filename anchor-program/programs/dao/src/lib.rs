@@ -7,11 +7,12 @@ pub mod instructions;
 pub mod state;
 pub mod config;
 pub mod event;
+pub mod utils;
 
 declare_id!("3LDehVNaAgFqvjo1cPg96j8tKUReLrpsKpW321fb8uyR");
 use instructions::*;
-use crate::state::ProposalType;
-use crate::instructions::staker_proposal::VoteChoice;
+use crate::state::{GoverningToken, ProposalInstruction, ProposalOption, ProposalType, QuorumMode, VoterPopulation, VotingMode};
+use crate::state::VoteChoice;
 
 #[program]
 pub mod dao {
@@ -20,35 +21,75 @@ pub mod dao {
 
     use super::*;
     ///初始化Dao
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_dao(
         ctx: Context<InitializeDao>,
         threshold: u8,
         vote_duration: i64,
-        quorum: u32,
+        quorum_mode: QuorumMode,
+        quorum: u64,
         staking_yield_rate: u16,
         pass_threshold_percentage: u8,
         min_staking_amount: u64,
+        voting_mode: VotingMode,
+        hold_up_time: i64,
+        execution_window: i64,
+        proposal_deposit: u64,
+        proposal_retention_period: i64,
+        unbonding_period: i64,
+        council_voting_mode: VotingMode,
+        council_quorum: u64,
     ) -> Result<()> {
         instructions::initialize_dao::initialize_dao(
             ctx,
             threshold,
             vote_duration,
+            quorum_mode,
             quorum,
             staking_yield_rate,
             pass_threshold_percentage,
             min_staking_amount,
+            voting_mode,
+            hold_up_time,
+            execution_window,
+            proposal_deposit,
+            proposal_retention_period,
+            unbonding_period,
+            council_voting_mode,
+            council_quorum,
         )
     }
 
-    /// 发起一个多签提案
+    /// 发起一个多签提案，可包含多个按顺序执行的操作；
+    /// 传入选项时为多选提案，只执行胜出选项的操作。voter_population 决定由哪类代币持有者投票
+    #[allow(clippy::too_many_arguments)]
     pub fn mul_create_propose(
         ctx: Context<Propose>, 
-        proposal_id: u64, 
-        proposal_type: ProposalType,
+        actions: Vec<ProposalType>,
+        options: Vec<ProposalOption>,
+        voter_population: VoterPopulation,
         title: String,
-        description: String
+        description: String,
+        metadata_uri: String,
+        content_hash: [u8; 32],
     ) -> Result<()> {
-        instructions::mul_proposal::mul_create_propose(ctx, proposal_id, proposal_type, title, description)
+        instructions::mul_proposal::mul_create_propose(ctx, actions, options, voter_population, title, description, metadata_uri, content_hash)
+    }
+
+    /// 在第一次批准前修改提案的标题、描述和链下文档
+    pub fn update_proposal_metadata(
+        ctx: Context<UpdateProposalMetadata>,
+        title: String,
+        description: String,
+        metadata_uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::mul_proposal::update_proposal_metadata(ctx, title, description, metadata_uri, content_hash)
+    }
+
+    /// 发起人提交草稿，提案进入多签审批。需按顺序传入每个任意指令操作的待执行指令账户
+    pub fn submit_proposal(ctx: Context<SubmitProposal>) -> Result<()> {
+        instructions::mul_proposal::submit_proposal(ctx)
     }
 
     /// 批准一个多签提案
@@ -56,29 +97,149 @@ pub mod dao {
         instructions::mul_proposal::mul_approve_propose(ctx)
     }
 
+    /// 否决一个多签提案，剩余签名者不足以达到阈值时提案被否决并关闭
+    pub fn mul_reject_propose(ctx: Context<Reject>) -> Result<()> {
+        instructions::mul_proposal::mul_reject_propose(ctx)
+    }
+
+    /// 发起人在投票开始前取消提案并关闭提案账户，已提交审批的质押者提案押金转入国库
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::mul_proposal::cancel_proposal(ctx)
+    }
+
+    /// 多签投票将质押者提案作为垃圾提案取消，达到阈值后押金转入国库
+    pub fn mul_cancel_spam_propose(ctx: Context<CancelSpamPropose>) -> Result<()> {
+        instructions::mul_proposal::mul_cancel_spam_propose(ctx)
+    }
+
+    /// 质押者缴纳押金发起提案，多签批准后进入投票
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_staker_proposal(
+        ctx: Context<CreateStakerProposal>,
+        actions: Vec<ProposalType>,
+        options: Vec<ProposalOption>,
+        voter_population: VoterPopulation,
+        title: String,
+        description: String,
+        metadata_uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::staker_proposal::create_staker_proposal(ctx, actions, options, voter_population, title, description, metadata_uri, content_hash)
+    }
+
+    /// 提案进入投票后退还质押者押金（无需权限）
+    pub fn refund_proposal_deposit(ctx: Context<RefundProposalDeposit>) -> Result<()> {
+        instructions::staker_proposal::refund_proposal_deposit(ctx)
+    }
+
+    /// 为任意指令提案写入待执行的指令
+    pub fn add_proposal_transaction(
+        ctx: Context<AddProposalTransaction>,
+        action_index: u8,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        instructions::mul_proposal::add_proposal_transaction(ctx, action_index, instructions)
+    }
+
     /// 领取定期支付
     pub fn claim_payment(ctx: Context<ClaimPayment>) -> Result<()> {
         instructions::claim_payment::claim_payment(ctx)
     }
     
-    /// 质押治理代币
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
-        instructions::stake::stake(ctx, amount)
+    /// 质押社区或理事会治理代币，可选锁仓以提高投票权重
+    pub fn stake(
+        ctx: Context<Stake>,
+        amount: u64,
+        lock_duration: Option<i64>,
+        governing_token: GoverningToken,
+    ) -> Result<()> {
+        instructions::stake::stake(ctx, amount, lock_duration, governing_token)
+    }
+
+    /// 延长质押锁仓
+    pub fn extend_lock(ctx: Context<ExtendLock>, lock_duration: i64, governing_token: GoverningToken) -> Result<()> {
+        instructions::extend_lock::extend_lock(ctx, lock_duration, governing_token)
+    }
+
+    /// 赎回部分或全部已质押的代币，代币进入解绑期
+    pub fn unstake(ctx: Context<Unstake>, amount: u64, governing_token: GoverningToken) -> Result<()> {
+        instructions::unstake::unstake(ctx, amount, governing_token)
+    }
+
+    /// 解绑期结束后提取代币
+    pub fn withdraw_unbonded(ctx: Context<WithdrawUnbonded>, governing_token: GoverningToken) -> Result<()> {
+        instructions::withdraw_unbonded::withdraw_unbonded(ctx, governing_token)
     }
 
-    /// 赎回所有已质押的代币
-    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
-        instructions::unstake::unstake(ctx)
+    /// 向奖励金库注入奖励
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        instructions::rewards::fund_rewards(ctx, amount)
     }
 
-    /// 对提案进行投票
-    pub fn vote(ctx: Context<Vote>, choice: VoteChoice) -> Result<()> {
+    /// 领取累计的质押奖励
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::rewards::claim_rewards(ctx)
+    }
+
+    /// 对提案进行投票，可合并委托给自己的投票权
+    pub fn vote<'info>(ctx: Context<'_, '_, 'info, 'info, Vote<'info>>, choice: VoteChoice) -> Result<()> {
         instructions::staker_proposal::vote(ctx, choice)
     }
 
-    /// 执行一个已通过的提案
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
-        instructions::staker_proposal::execute_proposal(ctx)
+    /// 守护者否决已通过（或投票期结束后计票通过）但尚未执行的提案
+    pub fn veto_proposal(ctx: Context<VetoProposal>, reason: String) -> Result<()> {
+        instructions::staker_proposal::veto_proposal(ctx, reason)
+    }
+
+    /// 在投票截止前修改投票选项
+    pub fn change_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeVote<'info>>,
+        new_choice: VoteChoice,
+    ) -> Result<()> {
+        instructions::staker_proposal::change_vote(ctx, new_choice)
+    }
+
+    /// 在投票截止前撤回投票
+    pub fn relinquish_vote(ctx: Context<RelinquishVote>) -> Result<()> {
+        instructions::staker_proposal::relinquish_vote(ctx)
+    }
+
+    /// 委托投票权
+    pub fn delegate(ctx: Context<Delegate>) -> Result<()> {
+        instructions::delegation::delegate(ctx)
+    }
+
+    /// 收回委托
+    pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+        instructions::delegation::undelegate(ctx)
+    }
+
+    /// 执行一个已通过的提案，max_actions 为空时执行全部剩余操作
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+        max_actions: Option<u8>,
+    ) -> Result<()> {
+        instructions::staker_proposal::execute_proposal(ctx, max_actions)
+    }
+
+    /// 投票结束后计票并记录结果（无需权限）
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::staker_proposal::finalize_proposal(ctx)
+    }
+
+    /// 提案结束后关闭投票记录，租金返还给投票记录的付款人（无需权限）
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        instructions::staker_proposal::close_vote_record(ctx)
+    }
+
+    /// 提案结束且超过保留时间后关闭提案账户，租金返还给发起人（无需权限）
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        instructions::staker_proposal::close_proposal(ctx)
+    }
+
+    /// 提案结束或已被关闭后关闭待执行指令账户，租金返还给发起人（无需权限）
+    pub fn close_proposal_transaction(ctx: Context<CloseProposalTransaction>) -> Result<()> {
+        instructions::staker_proposal::close_proposal_transaction(ctx)
     }
 
     /// 全局配置指令
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::error::DaoError;
+
 // DAO 全局状态账户
 #[account]
 #[derive(Default,InitSpace)]
@@ -29,8 +31,11 @@ pub struct DaoState {
     /// 总质押代币数量 (8)
     pub total_staked_amount: u64,
 
-    /// 法定人数：执行提案时，必须达到的最少投票人数 (u32)
-    pub quorum: u32,
+    /// 法定人数的计算方式
+    pub quorum_mode: QuorumMode,
+
+    /// 法定人数，含义由 quorum_mode 决定：最少投票人数、最少投票权重或占快照总质押量的百分比 (u64)
+    pub quorum: u64,
 
     /// 质押年化收益率 (u16, e.g., 500 for 5%)
     pub staking_yield_rate: u16,
@@ -39,6 +44,411 @@ pub struct DaoState {
     pub pass_threshold_percentage: u8,
     /// 参与提案和投票的最小质押代币数 (u64)
     pub min_staking_amount: u64,
+
+    /// 投票权重的计算方式
+    pub voting_mode: VotingMode,
+
+    /// 按提案种类覆盖的治理规则，未配置的种类使用上面的默认参数
+    #[max_len(5)]
+    pub governance_rules: Vec<GovernanceRule>,
+
+    /// 投票通过后到允许执行之间的等待时间（秒）
+    pub hold_up_time: i64,
+
+    /// 允许执行的时间窗口（秒），超过后通过的提案过期
+    pub execution_window: i64,
+
+    /// 已创建的提案数量，新提案的 ID 依次递增，可用 0..proposal_count 枚举所有提案
+    pub proposal_count: u64,
+
+    /// 质押者发起提案时需缴纳的押金（lamports），进入投票后退还
+    pub proposal_deposit: u64,
+
+    /// 提案结束（执行、未通过、取消或过期）后保留的时间（秒），之后可关闭提案账户回收租金
+    pub proposal_retention_period: i64,
+
+    /// 赎回的解绑期（秒）：赎回的代币需等待该时间后才能提取
+    pub unbonding_period: i64,
+
+    /// 全局累计的每单位质押奖励，按 REWARD_PRECISION 放大
+    pub reward_per_token: u128,
+
+    /// 上次累计奖励的时间
+    pub last_reward_update: i64,
+
+    /// 奖励金库中尚未分配给质押者的奖励，耗尽后停止累计
+    pub undistributed_rewards: u64,
+
+    /// 守护者：可在时间锁期间否决已通过但尚未执行的提案
+    pub guardian: Option<Pubkey>,
+
+    /// 总质押量的历史快照，按时间升序
+    #[max_len(MAX_TOTAL_STAKED_CHECKPOINTS)]
+    pub total_staked_checkpoints: Vec<StakeCheckpoint>,
+
+    /// 理事会治理代币的 Mint 地址，为空时 DAO 只有社区代币
+    pub council_mint: Option<Pubkey>,
+
+    /// 理事会投票权重的计算方式
+    pub council_voting_mode: VotingMode,
+
+    /// 理事会的法定人数，按 quorum_mode 解释，不受治理规则覆盖
+    pub council_quorum: u64,
+
+    /// 理事会代币的总质押数量，不参与质押奖励
+    pub council_total_staked_amount: u64,
+
+    /// 理事会代币总质押量的历史快照，按时间升序
+    #[max_len(MAX_TOTAL_STAKED_CHECKPOINTS)]
+    pub council_total_staked_checkpoints: Vec<StakeCheckpoint>,
+}
+
+impl DaoState {
+    /// 获取某一类提案生效的治理规则
+    pub fn governance_rule(&self, kind: ProposalKind) -> GovernanceRule {
+        self.governance_rules
+            .iter()
+            .find(|rule| rule.kind == kind)
+            .copied()
+            .unwrap_or(GovernanceRule {
+                kind,
+                vote_duration: self.vote_duration,
+                quorum: self.quorum,
+                pass_threshold_percentage: self.pass_threshold_percentage,
+            })
+    }
+
+    /// 在总质押量变化后记录快照
+    pub fn record_total_staked_checkpoint(&mut self, timestamp: i64) {
+        push_checkpoint(
+            &mut self.total_staked_checkpoints,
+            MAX_TOTAL_STAKED_CHECKPOINTS,
+            timestamp,
+            self.total_staked_amount,
+        );
+    }
+
+    /// 查询某一时刻之前的总质押量
+    pub fn total_staked_at(&self, timestamp: i64) -> Result<u64> {
+        checkpoint_amount_before(&self.total_staked_checkpoints, MAX_TOTAL_STAKED_CHECKPOINTS, timestamp)
+    }
+
+    /// 查询某一时刻之前的理事会代币总质押量
+    pub fn council_total_staked_at(&self, timestamp: i64) -> Result<u64> {
+        checkpoint_amount_before(&self.council_total_staked_checkpoints, MAX_TOTAL_STAKED_CHECKPOINTS, timestamp)
+    }
+
+    /// 某种治理代币的 Mint 地址，未配置理事会代币时返回 None
+    pub fn governing_mint(&self, governing_token: GoverningToken) -> Option<Pubkey> {
+        match governing_token {
+            GoverningToken::Community => Some(self.token_mint),
+            GoverningToken::Council => self.council_mint,
+        }
+    }
+
+    /// 某种治理代币的投票权重计算方式
+    pub fn voting_mode_for(&self, governing_token: GoverningToken) -> VotingMode {
+        match governing_token {
+            GoverningToken::Community => self.voting_mode,
+            GoverningToken::Council => self.council_voting_mode,
+        }
+    }
+
+    /// 质押或赎回后更新对应代币的总质押量，并记录该代币的快照
+    pub fn change_total_staked(
+        &mut self,
+        governing_token: GoverningToken,
+        amount: u64,
+        increase: bool,
+        now: i64,
+    ) -> Result<()> {
+        let total = match governing_token {
+            GoverningToken::Community => &mut self.total_staked_amount,
+            GoverningToken::Council => &mut self.council_total_staked_amount,
+        };
+        *total = if increase { total.checked_add(amount) } else { total.checked_sub(amount) }
+            .ok_or(DaoError::ArithmeticOverflow)?;
+        match governing_token {
+            GoverningToken::Community => self.record_total_staked_checkpoint(now),
+            GoverningToken::Council => push_checkpoint(
+                &mut self.council_total_staked_checkpoints,
+                MAX_TOTAL_STAKED_CHECKPOINTS,
+                now,
+                self.council_total_staked_amount,
+            ),
+        }
+        Ok(())
+    }
+
+    /// 按 staking_yield_rate 累计截至 now 的奖励。
+    /// 累计量不超过未分配的奖励，奖励金库耗尽后停止累计
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_reward_update).max(0) as u128;
+        self.last_reward_update = now;
+        if elapsed == 0 || self.total_staked_amount == 0 || self.undistributed_rewards == 0 {
+            return Ok(());
+        }
+
+        let accrued = (self.total_staked_amount as u128)
+            .checked_mul(self.staking_yield_rate as u128)
+            .and_then(|v| v.checked_mul(elapsed))
+            .ok_or(DaoError::ArithmeticOverflow)?
+            / (YIELD_RATE_DENOMINATOR * SECONDS_PER_YEAR);
+        let accrued = accrued.min(self.undistributed_rewards as u128);
+        if accrued == 0 {
+            return Ok(());
+        }
+
+        self.undistributed_rewards -= accrued as u64;
+        self.reward_per_token = self
+            .reward_per_token
+            .checked_add(accrued * REWARD_PRECISION / self.total_staked_amount as u128)
+            .ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 当前可通过治理修改的参数
+    pub fn parameters(&self) -> DaoParameters {
+        DaoParameters {
+            vote_duration: self.vote_duration,
+            quorum_mode: self.quorum_mode,
+            quorum: self.quorum,
+            staking_yield_rate: self.staking_yield_rate,
+            pass_threshold_percentage: self.pass_threshold_percentage,
+            min_staking_amount: self.min_staking_amount,
+            voting_mode: self.voting_mode,
+            proposal_deposit: self.proposal_deposit,
+            proposal_retention_period: self.proposal_retention_period,
+            unbonding_period: self.unbonding_period,
+            council_voting_mode: self.council_voting_mode,
+            council_quorum: self.council_quorum,
+        }
+    }
+}
+
+/// DAO 的治理参数，用于在事件中记录修改前后的值
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct DaoParameters {
+    pub vote_duration: i64,
+    pub quorum_mode: QuorumMode,
+    pub quorum: u64,
+    pub staking_yield_rate: u16,
+    pub pass_threshold_percentage: u8,
+    pub min_staking_amount: u64,
+    pub voting_mode: VotingMode,
+    pub proposal_deposit: u64,
+    pub proposal_retention_period: i64,
+    pub unbonding_period: i64,
+    pub council_voting_mode: VotingMode,
+    pub council_quorum: u64,
+}
+
+/// 治理代币的种类。两种代币各有独立的金库、质押账户和解绑记录
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug, InitSpace)]
+pub enum GoverningToken {
+    /// 社区代币，即 DaoState::token_mint
+    #[default]
+    Community,
+    /// 理事会代币，即 DaoState::council_mint
+    Council,
+}
+
+impl GoverningToken {
+    /// 治理金库 PDA 的种子前缀
+    pub fn vault_seed(&self) -> &'static [u8] {
+        match self {
+            GoverningToken::Community => b"governance_vault",
+            GoverningToken::Council => b"council_vault",
+        }
+    }
+
+    /// 质押账户 PDA 的种子前缀
+    pub fn stake_seed(&self) -> &'static [u8] {
+        match self {
+            GoverningToken::Community => b"stake_account",
+            GoverningToken::Council => b"council_stake_account",
+        }
+    }
+
+    /// 解绑记录 PDA 的种子前缀
+    pub fn unbonding_seed(&self) -> &'static [u8] {
+        match self {
+            GoverningToken::Community => b"unbonding",
+            GoverningToken::Council => b"council_unbonding",
+        }
+    }
+}
+
+/// 提案的投票人群
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug, InitSpace)]
+pub enum VoterPopulation {
+    /// 社区代币质押者投票
+    #[default]
+    Community,
+    /// 理事会代币质押者投票
+    Council,
+    /// 两者分别计票，都需达到法定人数并通过
+    Both,
+}
+
+impl VoterPopulation {
+    /// 该人群是否包含某种治理代币的质押者
+    pub fn includes(&self, governing_token: GoverningToken) -> bool {
+        match self {
+            VoterPopulation::Community => governing_token == GoverningToken::Community,
+            VoterPopulation::Council => governing_token == GoverningToken::Council,
+            VoterPopulation::Both => true,
+        }
+    }
+
+    /// 计入提案主计票字段（yes_votes 等）的代币：仅理事会投票时为理事会代币，否则为社区代币
+    pub fn primary_token(&self) -> GoverningToken {
+        match self {
+            VoterPopulation::Council => GoverningToken::Council,
+            _ => GoverningToken::Community,
+        }
+    }
+}
+
+/// 每单位质押奖励的放大倍数
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+/// staking_yield_rate 的分母（500 表示 5%）
+pub const YIELD_RATE_DENOMINATOR: u128 = 10_000;
+/// 一年的秒数，用于按年化收益率计算奖励
+pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+
+/// 最长锁仓时间（4 年）
+pub const MAX_LOCK_DURATION: i64 = 4 * 365 * 24 * 60 * 60;
+/// 锁仓倍数的基点（1 倍）
+pub const LOCK_MULTIPLIER_BASE: u16 = 10_000;
+/// 锁满 MAX_LOCK_DURATION 时的最大倍数（3 倍）
+pub const MAX_LOCK_MULTIPLIER: u16 = 30_000;
+
+/// 按锁仓时长线性计算倍数
+fn lock_multiplier_for(duration: i64) -> u16 {
+    let duration = duration.clamp(0, MAX_LOCK_DURATION) as u64;
+    let bonus = (MAX_LOCK_MULTIPLIER - LOCK_MULTIPLIER_BASE) as u64 * duration / MAX_LOCK_DURATION as u64;
+    LOCK_MULTIPLIER_BASE + bonus as u16
+}
+
+/// 每个质押账户保留的快照条数。提案批准后同一账户在不同时刻质押或赎回超过该次数，
+/// 批准时的快照会被丢弃，之后用该账户对此提案投票会失败（StakeHistoryTruncated）
+pub const MAX_STAKE_CHECKPOINTS: usize = 16;
+/// DaoState 保留的总质押量快照条数
+pub const MAX_TOTAL_STAKED_CHECKPOINTS: usize = 32;
+
+/// 质押数量快照：从 timestamp 起生效的数量
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default, InitSpace)]
+pub struct StakeCheckpoint {
+    pub timestamp: i64,
+    pub amount: u64,
+}
+
+/// 追加一条快照。同一时间戳的记录直接覆盖，超出上限时丢弃最早的记录
+fn push_checkpoint(checkpoints: &mut Vec<StakeCheckpoint>, max_len: usize, timestamp: i64, amount: u64) {
+    if let Some(last) = checkpoints.last_mut() {
+        if last.timestamp == timestamp {
+            last.amount = amount;
+            return;
+        }
+    }
+    if checkpoints.len() >= max_len {
+        checkpoints.remove(0);
+    }
+    checkpoints.push(StakeCheckpoint { timestamp, amount });
+}
+
+/// 返回严格早于 timestamp 的最后一条快照的数量，同一秒内的变化不计入。
+/// 没有更早的快照时：记录未满说明从未丢弃过快照，此前确实没有质押，视为 0；
+/// 记录已满则无法排除更早的快照已被丢弃，返回错误而不是把权重当作 0
+fn checkpoint_amount_before(checkpoints: &[StakeCheckpoint], max_len: usize, timestamp: i64) -> Result<u64> {
+    match checkpoints.iter().rev().find(|c| c.timestamp < timestamp) {
+        Some(checkpoint) => Ok(checkpoint.amount),
+        None if checkpoints.len() < max_len => Ok(0),
+        None => err!(DaoError::StakeHistoryTruncated),
+    }
+}
+
+/// 法定人数的计算方式
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum QuorumMode {
+    /// 参与投票的人数（含被委托人）不少于 quorum
+    MinVoters,
+    /// 投票总权重（含弃权）不少于 quorum
+    #[default]
+    MinWeight,
+    /// 投票者换算前的质押数量（含弃权）不少于快照时总质押量的 quorum%
+    PercentOfTotalStaked,
+}
+
+impl QuorumMode {
+    /// 验证 quorum 在该模式下是否有效
+    pub fn validate(&self, quorum: u64) -> Result<()> {
+        match self {
+            QuorumMode::MinVoters => require!(quorum <= u32::MAX as u64, DaoError::InvalidQuorum),
+            QuorumMode::MinWeight => {}
+            QuorumMode::PercentOfTotalStaked => require!(quorum <= 100, DaoError::InvalidQuorum),
+        }
+        Ok(())
+    }
+}
+
+/// 投票权重的计算方式
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum VotingMode {
+    /// 权重等于（锁仓加权后的）质押数量
+    #[default]
+    Linear,
+    /// 权重等于（锁仓加权后的）质押数量的整数平方根，削弱大户的影响
+    Quadratic,
+}
+
+impl VotingMode {
+    /// 将质押权重换算为实际计入的投票权重
+    pub fn apply(&self, weight: u64) -> u64 {
+        match self {
+            VotingMode::Linear => weight,
+            VotingMode::Quadratic => isqrt(weight),
+        }
+    }
+}
+
+/// 整数平方根（向下取整）
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // 牛顿迭代，初值不小于真实结果，之后单调递减
+    let mut x = n / 2 + 1;
+    let mut y = (x + n / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// 提案种类，作为治理规则表的键
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ProposalKind {
+    AddRecurringPayment,
+    UpdateDao,
+    WithdrawTreasury,
+    ExecuteInstructions,
+    FundRewards,
+}
+
+/// 某一类提案的投票规则
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, InitSpace)]
+pub struct GovernanceRule {
+    /// 规则适用的提案种类
+    pub kind: ProposalKind,
+    /// 投票持续时长（秒）
+    pub vote_duration: i64,
+    /// 法定人数，按 DAO 的 quorum_mode 解释
+    pub quorum: u64,
+    /// 投票通过的权重百分比
+    pub pass_threshold_percentage: u8,
 }
 //定义了用于记录每个用户质押信息的新账户 StakeAccount
 #[account]
@@ -50,7 +460,91 @@ pub struct StakeAccount {
     pub dao_state: Pubkey,
     /// 质押的代币数量
     pub amount: u64,
+    /// 锁定截止时间：用户投票的提案中最晚的投票结束时间，在此之前不可赎回
+    pub locked_until: i64,
+    /// 质押数量的历史快照，按时间升序
+    #[max_len(MAX_STAKE_CHECKPOINTS)]
+    pub checkpoints: Vec<StakeCheckpoint>,
+    /// 上次结算时的全局每单位质押奖励
+    pub reward_per_token_paid: u128,
+    /// 已结算但尚未领取的奖励
+    pub pending_rewards: u64,
+    /// 锁仓开始（或最近一次延长）的时间
+    pub lock_start: i64,
+    /// 锁仓到期时间，到期前不可赎回
+    pub lock_end: i64,
+    /// 锁仓开始时的投票权重倍数（基点，10000 为 1 倍），到期前线性衰减到 1 倍
+    pub lock_multiplier: u16,
+    /// 质押的治理代币种类
+    pub governing_token: GoverningToken,
+}
+
+impl StakeAccount {
+    /// 在质押数量变化后记录快照
+    pub fn record_checkpoint(&mut self, timestamp: i64) {
+        push_checkpoint(&mut self.checkpoints, MAX_STAKE_CHECKPOINTS, timestamp, self.amount);
+    }
+
+    /// 查询某一时刻之前的质押数量
+    pub fn amount_at(&self, timestamp: i64) -> Result<u64> {
+        checkpoint_amount_before(&self.checkpoints, MAX_STAKE_CHECKPOINTS, timestamp)
+    }
+
+    /// 锁仓到 lock_end，按剩余锁仓时间重新计算倍数
+    pub fn set_lock(&mut self, now: i64, lock_end: i64) {
+        self.lock_start = now;
+        self.lock_end = lock_end;
+        self.lock_multiplier = lock_multiplier_for(lock_end.saturating_sub(now));
+    }
+
+    /// 某一时刻的投票权重倍数（基点）
+    pub fn lock_multiplier_at(&self, timestamp: i64) -> u64 {
+        let base = LOCK_MULTIPLIER_BASE as u64;
+        if timestamp >= self.lock_end || self.lock_end <= self.lock_start {
+            return base;
+        }
+        let bonus = (self.lock_multiplier as u64).saturating_sub(base) as u128;
+        let remaining = (self.lock_end - timestamp) as u128;
+        let duration = (self.lock_end - self.lock_start) as u128;
+        base + (bonus * remaining.min(duration) / duration) as u64
+    }
+
+    /// 投票权重：快照时的质押数量乘以 now 时的锁仓倍数
+    pub fn voting_weight(&self, snapshot: i64, now: i64) -> Result<u64> {
+        let weight = self.amount_at(snapshot)? as u128 * self.lock_multiplier_at(now) as u128
+            / LOCK_MULTIPLIER_BASE as u128;
+        u64::try_from(weight).map_err(|_| DaoError::ArithmeticOverflow.into())
+    }
+
+    /// 按全局累计值结算奖励，必须在质押数量变化之前、DaoState::update_rewards 之后调用
+    pub fn settle_rewards(&mut self, reward_per_token: u128) -> Result<()> {
+        let earned = (self.amount as u128)
+            .checked_mul(reward_per_token.saturating_sub(self.reward_per_token_paid))
+            .ok_or(DaoError::ArithmeticOverflow)?
+            / REWARD_PRECISION;
+        self.pending_rewards = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.pending_rewards.checked_add(earned))
+            .ok_or(DaoError::ArithmeticOverflow)?;
+        self.reward_per_token_paid = reward_per_token;
+        Ok(())
+    }
+}
+// --- 解绑中的代币账户 ---
+// 每个质押者一个，多次赎回的数量累加，解绑截止时间按最后一次赎回重新计算
+#[account]
+#[derive(InitSpace, Default)]
+pub struct UnbondingAccount {
+    /// 质押者的公钥
+    pub staker: Pubkey,
+    /// 关联的 DAO State 账户
+    pub dao_state: Pubkey,
+    /// 等待提取的代币数量，不计入投票权重和总质押量
+    pub amount: u64,
+    /// 可以提取的时间
+    pub release_at: i64,
 }
+
 /// 定义了可以对 DAO 进行的修改操作
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Copy)]
 #[derive(InitSpace)]
@@ -61,6 +555,38 @@ pub enum DaoUpdateAction {
     RemoveSigner { signer_to_remove: Pubkey },
     /// 修改提案通过的阈值
     ChangeThreshold { new_threshold: u8 },
+    /// 修改执行前的等待时间和执行窗口
+    ChangeTimelock { hold_up_time: i64, execution_window: i64 },
+    /// 任命（或更换）守护者
+    AppointGuardian { guardian: Pubkey },
+    /// 撤销守护者
+    RemoveGuardian,
+    /// 修改质押者提案押金
+    ChangeProposalDeposit { proposal_deposit: u64 },
+    /// 修改法定人数的计算方式和数值
+    ChangeQuorum { quorum_mode: QuorumMode, quorum: u64 },
+    /// 修改默认投票持续时长
+    ChangeVoteDuration { vote_duration: i64 },
+    /// 修改质押年化收益率
+    ChangeStakingYieldRate { staking_yield_rate: u16 },
+    /// 修改默认投票通过的权重百分比
+    ChangePassThreshold { pass_threshold_percentage: u8 },
+    /// 修改参与提案和投票的最小质押数量
+    ChangeMinStakingAmount { min_staking_amount: u64 },
+    /// 修改投票权重的计算方式
+    ChangeVotingMode { voting_mode: VotingMode },
+    /// 修改理事会投票权重的计算方式
+    ChangeCouncilVotingMode { council_voting_mode: VotingMode },
+    /// 修改理事会的法定人数
+    ChangeCouncilQuorum { council_quorum: u64 },
+    /// 修改已结束提案的保留时间
+    ChangeProposalRetention { proposal_retention_period: i64 },
+    /// 修改赎回的解绑期
+    ChangeUnbondingPeriod { unbonding_period: i64 },
+    /// 设置（新增或替换）某一类提案的治理规则
+    SetGovernanceRule { rule: GovernanceRule },
+    /// 移除某一类提案的治理规则，恢复使用 DAO 默认参数
+    RemoveGovernanceRule { kind: ProposalKind },
 }
 /// 定期支付 (4 + N * (32 + 8 + 1 + 8 + 8)) -> (32 recipient + 8 amount + 1 currency + 8 interval + 8 next_payment = 57)
 #[account]
@@ -106,8 +632,56 @@ pub enum  ProposalType {
         amount: u64,
         recipient: Pubkey,
     },
+    /// 提案：以国库 PDA 签名执行任意指令，指令内容存放在 ProposalTransaction 账户中
+    ExecuteInstructions {
+        instruction_count: u8,
+    },
+    /// 提案：从国库的治理代币账户向奖励金库注资，计入待分配奖励
+    FundRewards {
+        amount: u64,
+    },
+}
+
+impl ProposalType {
+    /// 提案所属的种类
+    pub fn kind(&self) -> ProposalKind {
+        match self {
+            ProposalType::AddRecurringPayment { .. } => ProposalKind::AddRecurringPayment,
+            ProposalType::UpdateDao { .. } => ProposalKind::UpdateDao,
+            ProposalType::WithdrawTreasury { .. } => ProposalKind::WithdrawTreasury,
+            ProposalType::ExecuteInstructions { .. } => ProposalKind::ExecuteInstructions,
+            ProposalType::FundRewards { .. } => ProposalKind::FundRewards,
+        }
+    }
+}
+
+
+/// 多选提案最多包含的选项数量
+pub const MAX_PROPOSAL_OPTIONS: usize = 5;
+/// 选项标签的最大长度
+pub const MAX_OPTION_LABEL_LEN: usize = 32;
+
+/// 多选提案的选项
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, InitSpace)]
+pub struct ProposalOption {
+    /// 选项标签
+    #[max_len(MAX_OPTION_LABEL_LEN)]
+    pub label: String,
+    /// 选项胜出时执行的操作在 actions 中的下标，为空表示不执行任何操作
+    pub action_index: Option<u8>,
+    /// 该选项获得的投票权重，创建时必须为 0
+    pub votes: u64,
+    /// 投给该选项的人数（含委托人），创建时必须为 0
+    pub voter_count: u32,
+    /// 投给该选项的换算前质押数量之和，创建时必须为 0
+    pub raw_votes: u64,
 }
 
+/// 否决理由的最大长度
+pub const MAX_VETO_REASON_LEN: usize = 200;
+
+/// 单个提案最多包含的操作数量
+pub const MAX_PROPOSAL_ACTIONS: usize = 5;
 
 // --- 质押投票的提案账户 ---
 #[account]
@@ -115,46 +689,525 @@ pub enum  ProposalType {
 pub struct Proposal {
     /// 关联的 DAO State 账户地址
     pub dao_state: Pubkey,
-    /// 提案发起人（多签者）
+    /// 提案发起人（多签者或质押者）
     pub proposer: Pubkey,
     /// 提案的唯一ID
     pub proposal_id: u64,
-    /// 提案的具体操作
-    pub proposal_type: ProposalType,
+    /// 提案包含的操作，执行时按顺序依次执行
+    #[max_len(MAX_PROPOSAL_ACTIONS)]
+    pub actions: Vec<ProposalType>,
+    /// 已执行的操作数量，支持分多笔交易继续执行
+    pub executed_actions: u8,
+    /// 多选提案的选项；为空时为普通的赞成/反对提案
+    #[max_len(MAX_PROPOSAL_OPTIONS)]
+    pub options: Vec<ProposalOption>,
+    /// 参与投票的人群
+    pub voter_population: VoterPopulation,
     /// 已批准该提案的签名者列表
     #[max_len(5)]
     pub approvals: Vec<Pubkey>,
-    /// 提案的标题
-    #[max_len(50)]
-    pub title: String,
-    /// 提案的描述
-    #[max_len(200)]
-    pub description: String,
-    /// 赞成票总数 (基于质押权重)
+    /// 已否决该提案的签名者列表
+    #[max_len(5)]
+    pub rejections: Vec<Pubkey>,
+    /// 投票将该提案作为垃圾提案取消的签名者列表，达到阈值后没收押金
+    #[max_len(5)]
+    pub spam_votes: Vec<Pubkey>,
+    /// 赞成票总数 (基于质押权重)。以下计票字段记录 voter_population.primary_token() 的投票
     pub yes_votes: u64,
     /// 反对票总数 (基于质押权重)
     pub no_votes: u64,
+    /// 弃权票总数 (计入法定人数，不计入通过比例)
+    pub abstain_votes: u64,
     /// 参与投票的总人数
     pub voter_count: u32,
+    /// 投票者（含委托）换算前的快照质押数量之和，用于按总质押量百分比计算法定人数
+    pub raw_votes: u64,
     /// 提案投票结束时间戳
     pub end_time: i64,
-    /// 提案是否已执行
-    pub executed: bool,
+    /// 提案当前所处的生命周期状态
+    pub state: ProposalState,
     /// 提案创建时间
     pub created_at: i64,
-    /// 多签批准时间
+    /// 多签批准时间，同时作为投票权重的快照时间
     pub approved_at: Option<i64>,
+    /// 快照时刻主计票代币的总质押量
+    pub snapshot_total_staked: u64,
+    /// 两者共同投票时理事会的单独计票
+    pub council_votes: VoteTally,
+    /// 两者共同投票时，快照时刻的理事会总质押量
+    pub snapshot_council_total_staked: u64,
+    /// 批准时锁定的法定人数计算方式，之后修改 DAO 参数不影响本提案的计票
+    pub quorum_mode: QuorumMode,
+    /// 批准时锁定的主计票法定人数
+    pub quorum: u64,
+    /// 两者共同投票时，批准时锁定的理事会法定人数
+    pub council_quorum: u64,
+    /// 批准时锁定的通过比例
+    pub pass_threshold_percentage: u8,
+    /// 批准时锁定的社区代币投票权重计算方式
+    pub voting_mode: VotingMode,
+    /// 批准时锁定的理事会投票权重计算方式
+    pub council_voting_mode: VotingMode,
+    /// 最早可执行时间（投票通过后设置）
+    pub executable_at: i64,
+    /// 最晚可执行时间，超过后提案过期（投票通过后设置）
+    pub execution_deadline: i64,
+    /// 质押者提案托管在本账户中的押金（lamports），退还或没收后清零
+    pub deposit_amount: u64,
+    /// 提案进入最终状态（执行、未通过、取消或过期）的时间
+    pub finalized_at: Option<i64>,
+}
+
+/// 提案标题的最大长度
+pub const MAX_TITLE_LEN: usize = 128;
+/// 提案描述的最大长度
+pub const MAX_DESCRIPTION_LEN: usize = 2048;
+/// 链下提案文档地址的最大长度
+pub const MAX_METADATA_URI_LEN: usize = 256;
+
+// --- 提案元数据账户 ---
+// 大小按实际文本长度计算，因此不使用 InitSpace
+#[account]
+pub struct ProposalMetadata {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 提案的标题
+    pub title: String,
+    /// 提案的描述
+    pub description: String,
+    /// 链下提案文档的地址（如 IPFS / Arweave）
+    pub metadata_uri: String,
+    /// 链下提案文档的内容哈希
+    pub content_hash: [u8; 32],
+}
+
+impl ProposalMetadata {
+    /// 存放给定文本所需的账户空间（含 discriminator）
+    pub fn space(title: &str, description: &str, metadata_uri: &str) -> usize {
+        8 + 32 + (4 + title.len()) + (4 + description.len()) + (4 + metadata_uri.len()) + 32
+    }
+
+    /// 验证文本长度
+    pub fn validate(title: &str, description: &str, metadata_uri: &str) -> Result<()> {
+        require!(title.len() <= MAX_TITLE_LEN, DaoError::TitleTooLong);
+        require!(description.len() <= MAX_DESCRIPTION_LEN, DaoError::DescriptionTooLong);
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, DaoError::MetadataUriTooLong);
+        Ok(())
+    }
+}
+
+// --- 提案待执行指令账户 ---
+// 大小按实际指令内容计算，因此不使用 InitSpace
+#[account]
+pub struct ProposalTransaction {
+    /// 关联的提案账户
+    pub proposal: Pubkey,
+    /// 支付租金的提案发起人，关闭账户时租金返还给该账户
+    pub proposer: Pubkey,
+    /// 按顺序执行的指令列表
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+impl ProposalTransaction {
+    /// 存放给定指令列表所需的账户空间（含 discriminator）
+    pub fn space(instructions: &[ProposalInstruction]) -> usize {
+        8 + 32 + 32 + 4 + instructions.iter().map(ProposalInstruction::space).sum::<usize>()
+    }
+}
+
+/// 序列化保存的单条指令
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalInstruction {
+    /// 被调用的程序
+    pub program_id: Pubkey,
+    /// 指令涉及的账户
+    pub accounts: Vec<ProposalAccountMeta>,
+    /// 指令数据
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    fn space(&self) -> usize {
+        32 + 4 + self.accounts.len() * (32 + 1 + 1) + 4 + self.data.len()
+    }
+
+    /// 指令中要求签名的账户是否只有 signer（国库 PDA）。
+    /// 执行者等交易签名者的签名权限不能被传入提案指令
+    pub fn only_signed_by(&self, signer: &Pubkey) -> bool {
+        self.accounts.iter().all(|meta| !meta.is_signer || meta.pubkey == *signer)
+    }
+}
+
+impl From<&ProposalInstruction> for anchor_lang::solana_program::instruction::Instruction {
+    fn from(ix: &ProposalInstruction) -> Self {
+        Self {
+            program_id: ix.program_id,
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data.clone(),
+        }
+    }
+}
+
+/// 序列化保存的账户元信息
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// 提案的生命周期状态
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ProposalState {
+    /// 草稿：发起人可写入指令、修改元数据，提交后才能被批准
+    Draft,
+    /// 已提交，等待多签批准
+    PendingApproval,
+    /// 多签已批准，质押者投票中
+    Voting,
+    /// 投票通过，等待执行
+    Succeeded,
+    /// 投票未通过（未达法定人数或赞成比例不足）
+    Defeated,
+    /// 已取消
+    Cancelled,
+    /// 投票通过但未在有效期内执行
+    Expired,
+    /// 已执行
+    Executed,
+    /// 被多签否决：剩余签名者已不足以达到批准阈值
+    Rejected,
+}
+
+impl Proposal {
+    /// 提案适用的治理规则：包含多个操作时，各项参数取所有操作中最严格的一个
+    pub fn governance_rule(&self, dao_state: &DaoState) -> GovernanceRule {
+        self.actions
+            .iter()
+            .map(|action| dao_state.governance_rule(action.kind()))
+            .reduce(|strictest, rule| GovernanceRule {
+                kind: strictest.kind,
+                vote_duration: strictest.vote_duration.max(rule.vote_duration),
+                quorum: strictest.quorum.max(rule.quorum),
+                pass_threshold_percentage: strictest
+                    .pass_threshold_percentage
+                    .max(rule.pass_threshold_percentage),
+            })
+            .unwrap_or(GovernanceRule {
+                kind: ProposalKind::UpdateDao,
+                vote_duration: dao_state.vote_duration,
+                quorum: dao_state.quorum,
+                pass_threshold_percentage: dao_state.pass_threshold_percentage,
+            })
+    }
+
+    /// 将投票权重计入对应选项
+    pub fn add_votes(&mut self, choice: VoteChoice, weight: u64) -> Result<()> {
+        let tally = self.votes_for(choice)?;
+        *tally = tally.checked_add(weight).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 从对应选项中撤回投票权重
+    pub fn remove_votes(&mut self, choice: VoteChoice, weight: u64) -> Result<()> {
+        let tally = self.votes_for(choice)?;
+        *tally = tally.checked_sub(weight).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 找到投票选项对应的计票字段：普通提案只接受赞成/反对/弃权，
+    /// 多选提案只接受选项下标和弃权
+    fn votes_for(&mut self, choice: VoteChoice) -> Result<&mut u64> {
+        let multiple_choice = !self.options.is_empty();
+        match choice {
+            VoteChoice::Yes if !multiple_choice => Ok(&mut self.yes_votes),
+            VoteChoice::No if !multiple_choice => Ok(&mut self.no_votes),
+            VoteChoice::Abstain => Ok(&mut self.abstain_votes),
+            VoteChoice::Option { index } => self
+                .options
+                .get_mut(index as usize)
+                .map(|option| &mut option.votes)
+                .ok_or(DaoError::InvalidVoteChoice.into()),
+            _ => err!(DaoError::InvalidVoteChoice),
+        }
+    }
+
+    /// 多选提案中得票最多的选项；得票为 0 或出现并列时没有胜出选项
+    pub fn winning_option(&self) -> Option<u8> {
+        let max_votes = self.options.iter().map(|option| option.votes).max()?;
+        let mut leaders = self.options.iter().enumerate().filter(|(_, option)| option.votes == max_votes);
+        let (index, _) = leaders.next()?;
+        if max_votes == 0 || leaders.next().is_some() {
+            return None;
+        }
+        Some(index as u8)
+    }
+
+    /// 执行时需要运行的操作下标：普通提案为全部操作，多选提案只运行胜出选项的操作
+    pub fn execution_plan(&self) -> Vec<usize> {
+        if self.options.is_empty() {
+            return (0..self.actions.len()).collect();
+        }
+        self.winning_option()
+            .and_then(|index| self.options[index as usize].action_index)
+            .map(|action_index| vec![action_index as usize])
+            .unwrap_or_default()
+    }
+
+    /// 进入投票阶段时锁定计票规则：法定人数计算方式、法定人数、通过比例和投票权重计算方式。
+    /// 理事会投票使用 DAO 的 council_quorum
+    pub fn snapshot_voting_rule(&mut self, dao_state: &DaoState) {
+        let rule = self.governance_rule(dao_state);
+        self.quorum_mode = dao_state.quorum_mode;
+        self.quorum = match self.voter_population.primary_token() {
+            GoverningToken::Community => rule.quorum,
+            GoverningToken::Council => dao_state.council_quorum,
+        };
+        self.council_quorum = dao_state.council_quorum;
+        self.pass_threshold_percentage = rule.pass_threshold_percentage;
+        self.voting_mode = dao_state.voting_mode;
+        self.council_voting_mode = dao_state.council_voting_mode;
+    }
+
+    /// 计入投票者的人数和换算前的质押数量；多选提案同时计入所选选项
+    pub fn add_voters(&mut self, choice: VoteChoice, count: u32, raw_stake: u64) -> Result<()> {
+        self.voter_count = self.voter_count.checked_add(count).ok_or(DaoError::ArithmeticOverflow)?;
+        self.raw_votes = self.raw_votes.checked_add(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        if let VoteChoice::Option { index } = choice {
+            let option = self.options.get_mut(index as usize).ok_or(DaoError::InvalidVoteChoice)?;
+            option.voter_count = option.voter_count.checked_add(count).ok_or(DaoError::ArithmeticOverflow)?;
+            option.raw_votes = option.raw_votes.checked_add(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// 撤回投票者的人数和换算前的质押数量
+    pub fn remove_voters(&mut self, choice: VoteChoice, count: u32, raw_stake: u64) -> Result<()> {
+        self.voter_count = self.voter_count.checked_sub(count).ok_or(DaoError::ArithmeticOverflow)?;
+        self.raw_votes = self.raw_votes.checked_sub(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        if let VoteChoice::Option { index } = choice {
+            let option = self.options.get_mut(index as usize).ok_or(DaoError::InvalidVoteChoice)?;
+            option.voter_count = option.voter_count.checked_sub(count).ok_or(DaoError::ArithmeticOverflow)?;
+            option.raw_votes = option.raw_votes.checked_sub(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// 是否达到批准时锁定的法定人数。普通提案按全部投票（含弃权）计算，
+    /// 多选提案只按胜出选项自身的票数、人数和质押数量计算；两者共同投票时需分别达到
+    pub fn quorum_reached(&self) -> bool {
+        let (total_votes, raw_votes, voter_count) = if self.options.is_empty() {
+            (
+                self.yes_votes as u128 + self.no_votes as u128 + self.abstain_votes as u128,
+                self.raw_votes,
+                self.voter_count,
+            )
+        } else {
+            let Some(index) = self.winning_option() else {
+                return false;
+            };
+            let winner = &self.options[index as usize];
+            (winner.votes as u128, winner.raw_votes, winner.voter_count)
+        };
+        let primary_reached = quorum_met(
+            self.quorum_mode,
+            self.quorum,
+            total_votes,
+            raw_votes,
+            voter_count,
+            self.snapshot_total_staked,
+        );
+        if self.voter_population != VoterPopulation::Both {
+            return primary_reached;
+        }
+        primary_reached
+            && quorum_met(
+                self.quorum_mode,
+                self.council_quorum,
+                self.council_votes.total_votes(),
+                self.council_votes.raw_votes,
+                self.council_votes.voter_count,
+                self.snapshot_council_total_staked,
+            )
+    }
+
+    /// 赞成票是否超过批准时锁定的通过比例（弃权票不计入）；多选提案按相对多数决定，
+    /// 只需有唯一的得票最多的选项，不适用通过比例。两者共同投票时社区和理事会需分别通过
+    pub fn vote_passed(&self) -> bool {
+        if !self.options.is_empty() {
+            return self.winning_option().is_some();
+        }
+        let primary_passed = yes_share_passed(self.yes_votes, self.no_votes, self.pass_threshold_percentage);
+        if self.voter_population != VoterPopulation::Both {
+            return primary_passed;
+        }
+        primary_passed
+            && yes_share_passed(
+                self.council_votes.yes_votes,
+                self.council_votes.no_votes,
+                self.pass_threshold_percentage,
+            )
+    }
+
+    /// 标记提案投票通过，并按 DAO 的时间锁参数计算执行窗口
+    pub fn mark_succeeded(&mut self, dao_state: &DaoState) -> Result<()> {
+        self.state = ProposalState::Succeeded;
+        self.executable_at = self
+            .end_time
+            .checked_add(dao_state.hold_up_time)
+            .ok_or(DaoError::ArithmeticOverflow)?;
+        self.execution_deadline = self
+            .executable_at
+            .checked_add(dao_state.execution_window)
+            .ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 按批准时锁定的规则计票：返回投票结束后的最终状态（Succeeded 或 Defeated）
+    pub fn tally(&self) -> ProposalState {
+        if self.quorum_reached() && self.vote_passed() {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Defeated
+        }
+    }
+}
+
+/// 按法定人数的计算方式判断一个投票人群是否达到法定人数。
+/// 按总质押量百分比计算时比较换算前的质押数量，不受投票模式和锁仓倍数影响
+fn quorum_met(
+    quorum_mode: QuorumMode,
+    quorum: u64,
+    total_votes: u128,
+    raw_votes: u64,
+    voter_count: u32,
+    snapshot_total_staked: u64,
+) -> bool {
+    match quorum_mode {
+        QuorumMode::MinVoters => voter_count as u64 >= quorum,
+        QuorumMode::MinWeight => total_votes >= quorum as u128,
+        QuorumMode::PercentOfTotalStaked => {
+            raw_votes as u128 * 100 >= snapshot_total_staked as u128 * quorum as u128
+        }
+    }
+}
+
+/// 赞成票占赞成与反对票之和的比例是否超过通过比例
+fn yes_share_passed(yes_votes: u64, no_votes: u64, pass_threshold_percentage: u8) -> bool {
+    let total_votes = yes_votes as u128 + no_votes as u128;
+    let pass_threshold = total_votes * pass_threshold_percentage as u128 / 100;
+    yes_votes as u128 > pass_threshold
+}
+
+/// 单个投票人群的赞成/反对/弃权计票
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct VoteTally {
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
+    pub voter_count: u32,
+    /// 换算前的快照质押数量之和
+    pub raw_votes: u64,
+}
+
+impl VoteTally {
+    /// 将投票权重计入对应选项
+    pub fn add_votes(&mut self, choice: VoteChoice, weight: u64) -> Result<()> {
+        let tally = self.votes_for(choice)?;
+        *tally = tally.checked_add(weight).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 从对应选项中撤回投票权重
+    pub fn remove_votes(&mut self, choice: VoteChoice, weight: u64) -> Result<()> {
+        let tally = self.votes_for(choice)?;
+        *tally = tally.checked_sub(weight).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 计入一名投票者：投票人数和换算前的质押数量
+    pub fn add_voter(&mut self, raw_stake: u64) -> Result<()> {
+        self.voter_count = self.voter_count.checked_add(1).ok_or(DaoError::ArithmeticOverflow)?;
+        self.raw_votes = self.raw_votes.checked_add(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 撤回一名投票者
+    pub fn remove_voter(&mut self, raw_stake: u64) -> Result<()> {
+        self.voter_count = self.voter_count.checked_sub(1).ok_or(DaoError::ArithmeticOverflow)?;
+        self.raw_votes = self.raw_votes.checked_sub(raw_stake).ok_or(DaoError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// 投票总权重（含弃权）
+    pub fn total_votes(&self) -> u128 {
+        self.yes_votes as u128 + self.no_votes as u128 + self.abstain_votes as u128
+    }
+
+    fn votes_for(&mut self, choice: VoteChoice) -> Result<&mut u64> {
+        match choice {
+            VoteChoice::Yes => Ok(&mut self.yes_votes),
+            VoteChoice::No => Ok(&mut self.no_votes),
+            VoteChoice::Abstain => Ok(&mut self.abstain_votes),
+            VoteChoice::Option { .. } => err!(DaoError::InvalidVoteChoice),
+        }
+    }
+}
+
+/// 投票选项
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VoteChoice {
+    Yes,
+    No,
+    /// 弃权：计入法定人数，但不计入通过比例
+    Abstain,
+    /// 多选提案中选择第 index 个选项
+    Option { index: u8 },
 }
 
 // --- 投票记录账户 ---
 // 用于防止用户重复投票
 #[account]
-#[derive(InitSpace, Default)]
+#[derive(InitSpace)]
 pub struct VoteRecord {
     /// 关联的提案账户
     pub proposal: Pubkey,
     /// 投票人
     pub voter: Pubkey,
-    /// 投票人的投票权重 (当时质押的数量)
+    /// 实际计入主计票的投票权重（按对应代币的投票模式换算后）
     pub weight: u64,
+    /// 换算前的质押数量（快照时，含委托）
+    pub raw_stake: u64,
+    /// 两者共同投票时计入理事会计票的权重
+    pub council_weight: u64,
+    /// 两者共同投票时理事会代币换算前的质押数量
+    pub council_raw_stake: u64,
+    /// 投票选项
+    pub choice: VoteChoice,
+    /// 由受托人代为投票时记录受托人地址，权重已计入受托人的投票
+    pub delegate: Option<Pubkey>,
+    /// 本次投票合并的委托人数量
+    pub delegator_count: u32,
+}
+
+// --- 投票权委托账户 ---
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    /// 关联的 DAO State 账户
+    pub dao_state: Pubkey,
+    /// 委托人
+    pub delegator: Pubkey,
+    /// 受托人
+    pub delegate: Pubkey,
+    /// 委托时间
+    pub created_at: i64,
 }
\ No newline at end of file
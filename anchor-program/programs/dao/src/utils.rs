@@ -0,0 +1,114 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint,
+};
+use anchor_spl::token_interface::{self, TokenAccount, TransferChecked};
+
+/// 在指令内部创建一个由本程序拥有的 PDA 账户，
+/// 兼容该地址已被预先转入 lamports 的情况
+pub fn create_pda_account<'info>(
+    payer: AccountInfo<'info>,
+    target: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer = &[seeds];
+
+    if target.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                system_program::CreateAccount { from: payer, to: target },
+                signer,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let top_up = rent.saturating_sub(target.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer { from: payer, to: target.clone() },
+                ),
+                top_up,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate { account_to_allocate: target.clone() },
+                signer,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                system_program::Assign { account_to_assign: target },
+                signer,
+            ),
+            &crate::ID,
+        )?;
+    }
+    Ok(())
+}
+
+/// 从本程序拥有的账户中直接划转 lamports（如提案账户托管的押金）
+pub fn transfer_program_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    **from_lamports = from_lamports
+        .checked_sub(amount)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    **to_lamports = to_lamports
+        .checked_add(amount)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// 治理代币不支持的 Token-2022 扩展：
+/// - NonTransferable：代币无法转入金库
+/// - PermanentDelegate：委托人可以直接转走金库中的代币
+/// - TransferHook：转账需要额外的 hook 账户，金库的转账不会携带这些账户
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::NonTransferable,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::TransferHook,
+];
+
+/// 检查治理代币的 Mint 是否可以安全地用于质押金库。
+/// 旧版 Token Program 的 Mint 没有扩展，直接通过
+pub fn validate_governance_mint(mint_info: &AccountInfo) -> Result<()> {
+    if *mint_info.owner != anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let extensions = mint.get_extension_types()?;
+    require!(
+        !extensions.iter().any(|extension| UNSUPPORTED_MINT_EXTENSIONS.contains(extension)),
+        crate::error::DaoError::UnsupportedMintExtension
+    );
+    Ok(())
+}
+
+/// 将代币转入本程序的金库，返回金库实际收到的数量（扣除转账手续费后）
+pub fn transfer_into_vault<'info>(
+    cpi_context: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+    decimals: u8,
+) -> Result<u64> {
+    let balance_before = vault.amount;
+    token_interface::transfer_checked(cpi_context, amount, decimals)?;
+    vault.reload()?;
+    vault.amount
+        .checked_sub(balance_before)
+        .ok_or(crate::error::DaoError::ArithmeticOverflow.into())
+}
@@ -25,6 +25,7 @@ describe("01 - DAO Initialization Tests", () => {
   const tokenMint = Keypair.generate();
   let daoState: PublicKey;
   let governanceVault: PublicKey;
+  let rewardVault: PublicKey;
   let treasury: PublicKey;
   let config: PublicKey;
 
@@ -37,10 +38,19 @@ describe("01 - DAO Initialization Tests", () => {
   // --- Initial state ---
   const threshold = 1;
   const voteDuration = 1; // 1 second for testing
-  const quorum = 1;
+  const quorumMode = { minWeight: {} };
+  const quorum = new anchor.BN(1);
   const stakingYieldRate = 500; // 5%
   const passThresholdPercentage = 60; // 60%
   const minStakingAmount = new anchor.BN(100);
+  const votingMode = { linear: {} };
+  const holdUpTime = 0; // 测试中投票通过后可立即执行
+  const executionWindow = 7 * 24 * 60 * 60; // 7 days
+  const proposalDeposit = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+  const proposalRetentionPeriod = 30 * 24 * 60 * 60; // 30 days
+  const unbondingPeriod = 0; // 测试中赎回后可立即提取
+  const councilVotingMode = { linear: {} };
+  const councilQuorum = new anchor.BN(0); // 不配置理事会代币
 
   before(async () => {
     // --- Fund test accounts ---
@@ -66,6 +76,10 @@ describe("01 - DAO Initialization Tests", () => {
       [Buffer.from("governance_vault"), daoState.toBuffer()],
       program.programId
     );
+    [rewardVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), daoState.toBuffer()],
+      program.programId
+    );
     [treasury] = PublicKey.findProgramAddressSync(
       [Buffer.from("treasury"), daoState.toBuffer()],
       program.programId
@@ -137,10 +151,19 @@ describe("01 - DAO Initialization Tests", () => {
       .initializeDao(
         threshold,
         new anchor.BN(voteDuration),
+        quorumMode,
         quorum,
         stakingYieldRate,
         passThresholdPercentage,
-        minStakingAmount
+        minStakingAmount,
+        votingMode,
+        new anchor.BN(holdUpTime),
+        new anchor.BN(executionWindow),
+        proposalDeposit,
+        new anchor.BN(proposalRetentionPeriod),
+        new anchor.BN(unbondingPeriod),
+        councilVotingMode,
+        councilQuorum
       )
       .accounts({
         daoState: daoState,
@@ -148,6 +171,9 @@ describe("01 - DAO Initialization Tests", () => {
         treasury: treasury,
         tokenMint: tokenMint.publicKey,
         governanceVault: governanceVault,
+        rewardVault: rewardVault,
+        councilMint: null,
+        councilVault: null,
         config: config,
         systemProgram: SystemProgram.programId,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -168,6 +194,7 @@ describe("01 - DAO Initialization Tests", () => {
       admin,
       daoState,
       governanceVault,
+      rewardVault,
       treasury,
       config,
       tokenMint,
@@ -7,7 +7,40 @@ import {
   SystemProgram,
   PublicKey,
   LAMPORTS_PER_SOL,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getAccount,
+  getMintLen,
+} from "@solana/spl-token";
+import {
+  DECIMALS,
+  TestDao,
+  TestStaker,
+  airdrop,
+  approve,
+  createStaker,
+  createTestDao,
+  execute,
+  expectError,
+  propose,
+  proposeAndApprove,
+  proposeAsStaker,
+  stake,
+  tokens,
+  updateDao,
+  vote,
+  waitForSnapshot,
+  waitForVoteEnd,
+  withdrawTreasury,
+} from "./helpers.js";
 
 describe("02 - Multisig Proposal Tests", () => {
   // --- Get shared context from initialization tests ---
@@ -15,8 +48,34 @@ describe("02 - Multisig Proposal Tests", () => {
   let program: Program<Dao>;
   let admin: anchor.Wallet;
   let daoState: PublicKey;
+  let governanceVault: PublicKey;
+  let tokenMint: Keypair;
+  let staker: Keypair;
+  let stakerTokenAccount: PublicKey;
   let recipient: Keypair;
-  let proposalId: anchor.BN;
+
+  // 提案 ID 由链上计数器分配，PDA 按当前 proposalCount 推导
+  const nextProposal = async (): Promise<[PublicKey, anchor.BN]> => {
+    const daoStateAccount = await program.account.daoState.fetch(daoState);
+    const proposalId = daoStateAccount.proposalCount;
+    const [proposal] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        daoState.toBuffer(),
+        proposalId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    return [proposal, proposalId];
+  };
+
+  const proposalMetadataFor = (proposal: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal_metadata"), proposal.toBuffer()],
+      program.programId
+    )[0];
+  const metadataUri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+  const contentHash = Array(32).fill(7);
 
   before(async () => {
     // 等待初始化测试完成并获取共享上下文
@@ -28,8 +87,45 @@ describe("02 - Multisig Proposal Tests", () => {
     program = testContext.program;
     admin = testContext.admin;
     daoState = testContext.daoState;
+    governanceVault = testContext.governanceVault;
+    tokenMint = testContext.tokenMint;
+    staker = testContext.staker;
+    stakerTokenAccount = testContext.stakerTokenAccount;
     recipient = testContext.recipient;
-    proposalId = new anchor.BN(1);
+  });
+
+  it("Stakes tokens", async () => {
+    const stakeAmount = new anchor.BN(500 * 10 ** 6);
+    const [stakeAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_account"), daoState.toBuffer(), staker.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .stake(stakeAmount, null, { community: {} })
+      .accounts({
+        staker: staker.publicKey,
+        daoState: daoState,
+        stakerTokenAccount: stakerTokenAccount,
+        tokenMint: tokenMint.publicKey,
+        governanceVault: governanceVault,
+        stakeAccount: stakeAccount,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .signers([staker])
+      .rpc();
+
+    const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccount);
+    assert.ok(stakeAccountData.amount.eq(stakeAmount));
+
+    const vaultAccount = await getAccount(program.provider.connection, governanceVault);
+    assert.equal(vaultAccount.amount.toString(), stakeAmount.toString());
+
+    (global as any).stakeAccount = stakeAccount;
+
+    // 投票权重取多签批准时刻之前的质押快照，等待进入下一秒再批准提案
+    await new Promise((resolve) => setTimeout(resolve, 2000));
   });
 
   it("Creates a multisig proposal", async () => {
@@ -42,20 +138,14 @@ describe("02 - Multisig Proposal Tests", () => {
     const title = "Test Treasury Withdrawal Proposal";
     const description = "This is a test proposal to withdraw funds from treasury";
 
-    const [proposal] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("proposal"),
-        daoState.toBuffer(),
-        proposalId.toBuffer("le", 8),
-      ],
-      program.programId
-    );
+    const [proposal, proposalId] = await nextProposal();
 
     await program.methods
-      .mulCreatePropose(proposalId, proposalType, title, description)
+      .mulCreatePropose([proposalType], [], { community: {} }, title, description, metadataUri, contentHash)
       .accounts({
         daoState: daoState,
         proposal: proposal,
+        proposalMetadata: proposalMetadataFor(proposal),
         proposer: admin.publicKey,
         authority: admin.publicKey,
         systemProgram: SystemProgram.programId,
@@ -65,11 +155,29 @@ describe("02 - Multisig Proposal Tests", () => {
     // 验证提案创建
     const proposalAccount = await program.account.proposal.fetch(proposal);
     assert.equal(proposalAccount.proposalId.toString(), proposalId.toString());
-    assert.equal(proposalAccount.title, title);
-    assert.equal(proposalAccount.description, description);
+    const metadataAccount = await program.account.proposalMetadata.fetch(proposalMetadataFor(proposal));
+    assert.ok(metadataAccount.proposal.equals(proposal));
+    assert.equal(metadataAccount.title, title);
+    assert.equal(metadataAccount.description, description);
+    assert.equal(metadataAccount.metadataUri, metadataUri);
+    assert.deepEqual(metadataAccount.contentHash, contentHash);
     assert.equal(proposalAccount.approvals.length, 0);
     assert.isNull(proposalAccount.approvedAt);
-    assert.isFalse(proposalAccount.executed);
+    assert.deepEqual(proposalAccount.state, { draft: {} });
+
+    // 草稿不能被批准，提交后进入审批
+    await expectError(
+      program.methods
+        .mulApprovePropose()
+        .accounts({ daoState, proposal, approver: admin.publicKey, authority: admin.publicKey })
+        .rpc(),
+      "InvalidProposalState"
+    );
+    await program.methods
+      .submitProposal()
+      .accounts({ daoState, proposal, proposer: admin.publicKey })
+      .rpc();
+    assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { pendingApproval: {} });
 
     // 保存提案地址供后续测试使用
     (global as any).currentProposal = proposal;
@@ -96,13 +204,12 @@ describe("02 - Multisig Proposal Tests", () => {
     assert.equal(proposalAccount.approvals.length, 1);
     assert.ok(proposalAccount.approvedAt);
     assert.ok(proposalAccount.endTime > 0); // 应该设置投票结束时间
-    assert.isFalse(proposalAccount.executed);
+    assert.deepEqual(proposalAccount.state, { voting: {} });
 
     console.log(`Proposal approved. Voting ends at: ${new Date(proposalAccount.endTime.toNumber() * 1000)}`);
   });
 
   it("Creates a recurring payment proposal", async () => {
-    const proposalId2 = new anchor.BN(2);
     const proposalType = {
       addRecurringPayment: {
         recipient: recipient.publicKey,
@@ -114,25 +221,23 @@ describe("02 - Multisig Proposal Tests", () => {
     const title = "Test Recurring Payment Proposal";
     const description = "This is a test proposal to add a recurring payment";
 
-    const [proposal] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("proposal"),
-        daoState.toBuffer(),
-        proposalId2.toBuffer("le", 8),
-      ],
-      program.programId
-    );
+    const [proposal] = await nextProposal();
 
     await program.methods
-      .mulCreatePropose(proposalId2, proposalType, title, description)
+      .mulCreatePropose([proposalType], [], { community: {} }, title, description, metadataUri, contentHash)
       .accounts({
         daoState: daoState,
         proposal: proposal,
+        proposalMetadata: proposalMetadataFor(proposal),
         proposer: admin.publicKey,
         authority: admin.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
+    await program.methods
+      .submitProposal()
+      .accounts({ daoState, proposal, proposer: admin.publicKey })
+      .rpc();
 
     // 批准提案
     await program.methods
@@ -161,18 +266,295 @@ describe("02 - Multisig Proposal Tests", () => {
 
     // 验证第一个提案（国库提款）
     const proposal1Account = await program.account.proposal.fetch(proposal1);
-    assert.equal(proposal1Account.proposalId.toString(), "1");
+    assert.equal(proposal1Account.proposalId.toString(), "0");
     assert.ok(proposal1Account.approvedAt);
     assert.equal(proposal1Account.approvals.length, 1);
     assert.ok(proposal1Account.endTime > 0);
 
     // 验证第二个提案（定期支付）
     const proposal2Account = await program.account.proposal.fetch(proposal2);
-    assert.equal(proposal2Account.proposalId.toString(), "2");
+    assert.equal(proposal2Account.proposalId.toString(), "1");
     assert.ok(proposal2Account.approvedAt);
     assert.equal(proposal2Account.approvals.length, 1);
     assert.ok(proposal2Account.endTime > 0);
 
+    const daoStateAccount = await program.account.daoState.fetch(daoState);
+    assert.equal(daoStateAccount.proposalCount.toString(), "2");
+
     console.log("Both proposals are approved and ready for staker voting");
   });
+
+  describe("Staker proposal deposits", () => {
+    let dao: TestDao;
+    let proposer: TestStaker;
+    let deposit: number;
+
+    const refund = (proposal: PublicKey) =>
+      program.methods
+        .refundProposalDeposit()
+        .accounts({ proposal, proposer: proposer.publicKey })
+        .rpc();
+
+    before(async () => {
+      dao = await createTestDao(program);
+      deposit = (await program.account.daoState.fetch(dao.daoState)).proposalDeposit.toNumber();
+      proposer = await createStaker(dao, tokens(100));
+      await stake(dao, proposer, tokens(100));
+    });
+
+    it("Escrows the deposit and refunds it once the proposal enters voting", async () => {
+      const proposal = await proposeAsStaker(dao, proposer, [withdrawTreasury(LAMPORTS_PER_SOL, proposer.publicKey)]);
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.depositAmount.toNumber(), deposit);
+      await expectError(refund(proposal), "DepositNotRefundable");
+
+      await approve(dao, proposal);
+      const balanceBefore = await program.provider.connection.getBalance(proposer.publicKey);
+      await refund(proposal);
+
+      const balanceAfter = await program.provider.connection.getBalance(proposer.publicKey);
+      assert.equal(balanceAfter, balanceBefore + deposit);
+      assert.equal((await program.account.proposal.fetch(proposal)).depositAmount.toNumber(), 0);
+    });
+
+    it("Forfeits the deposit to the treasury when the multisig cancels it as spam", async () => {
+      const proposal = await proposeAsStaker(dao, proposer, [withdrawTreasury(LAMPORTS_PER_SOL, proposer.publicKey)]);
+      const treasuryBefore = await program.provider.connection.getBalance(dao.treasury);
+
+      await program.methods
+        .mulCancelSpamPropose()
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          treasury: dao.treasury,
+          signer: dao.authority.publicKey,
+        })
+        .signers([dao.authority])
+        .rpc();
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { cancelled: {} });
+      assert.equal(proposalAccount.spamVotes.length, 1);
+      assert.equal(proposalAccount.depositAmount.toNumber(), 0);
+      assert.equal(await program.provider.connection.getBalance(dao.treasury), treasuryBefore + deposit);
+      await expectError(refund(proposal), "NoProposalDeposit");
+    });
+  });
+
+  describe("Cancelling and rejecting proposals", () => {
+    let dao: TestDao;
+    let staker: TestStaker;
+    let deposit: number;
+
+    const cancel = (proposal: PublicKey, proposer: Keypair) =>
+      program.methods
+        .cancelProposal()
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          proposalMetadata: proposalMetadataFor(proposal),
+          treasury: dao.treasury,
+          proposer: proposer.publicKey,
+        })
+        .signers([proposer])
+        .rpc();
+
+    const isClosed = async (proposal: PublicKey) =>
+      (await program.provider.connection.getAccountInfo(proposal)) === null &&
+      (await program.provider.connection.getAccountInfo(proposalMetadataFor(proposal))) === null;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      deposit = (await program.account.daoState.fetch(dao.daoState)).proposalDeposit.toNumber();
+      staker = await createStaker(dao, tokens(100));
+      await stake(dao, staker, tokens(100));
+    });
+
+    it("Closes a multisig proposal cancelled by its proposer", async () => {
+      const proposal = await propose(dao, [withdrawTreasury(LAMPORTS_PER_SOL, staker.publicKey)]);
+      await cancel(proposal, dao.authority);
+      assert.isTrue(await isClosed(proposal));
+    });
+
+    it("Forfeits the deposit when a staker cancels their own proposal", async () => {
+      const proposal = await proposeAsStaker(dao, staker, [withdrawTreasury(LAMPORTS_PER_SOL, staker.publicKey)]);
+      const treasuryBefore = await program.provider.connection.getBalance(dao.treasury);
+
+      await cancel(proposal, staker.keypair);
+
+      assert.isTrue(await isClosed(proposal));
+      assert.equal(await program.provider.connection.getBalance(dao.treasury), treasuryBefore + deposit);
+    });
+
+    it("Returns the deposit when a staker cancels an unsubmitted draft", async () => {
+      const proposal = await proposeAsStaker(dao, staker, [withdrawTreasury(LAMPORTS_PER_SOL, staker.publicKey)], {
+        submit: false,
+      });
+      const treasuryBefore = await program.provider.connection.getBalance(dao.treasury);
+
+      await cancel(proposal, staker.keypair);
+
+      assert.isTrue(await isClosed(proposal));
+      assert.equal(await program.provider.connection.getBalance(dao.treasury), treasuryBefore);
+    });
+
+    it("Refunds the deposit when the multisig rejects a staker proposal", async () => {
+      const proposal = await proposeAsStaker(dao, staker, [withdrawTreasury(LAMPORTS_PER_SOL, staker.publicKey)]);
+      const proposerBefore = await program.provider.connection.getBalance(staker.publicKey);
+
+      // 唯一的签名者否决后已无法达到阈值，提案被否决并关闭
+      await program.methods
+        .mulRejectPropose()
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          proposalMetadata: proposalMetadataFor(proposal),
+          rejector: dao.authority.publicKey,
+          proposer: staker.publicKey,
+          authority: dao.authority.publicKey,
+        })
+        .signers([dao.authority])
+        .rpc();
+
+      assert.isTrue(await isClosed(proposal));
+      // 押金与两个账户的租金一起退还
+      assert.isAbove(await program.provider.connection.getBalance(staker.publicKey), proposerBefore + deposit);
+    });
+
+    it("Cannot cancel a proposal once voting has started", async () => {
+      const proposal = await proposeAsStaker(dao, staker, [withdrawTreasury(LAMPORTS_PER_SOL, staker.publicKey)]);
+      await approve(dao, proposal);
+      await expectError(cancel(proposal, staker.keypair), "InvalidProposalState");
+    });
+  });
+
+  describe("Token-2022 governance mints", () => {
+    // 创建带指定扩展的 Token-2022 Mint，扩展须在 InitializeMint 之前初始化
+    const createMint2022 = async (
+      authority: Keypair,
+      extensions: ExtensionType[],
+      initializeExtension: (mint: PublicKey) => TransactionInstruction
+    ): Promise<PublicKey> => {
+      const connection = program.provider.connection;
+      const mint = Keypair.generate();
+      const space = getMintLen(extensions);
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports: await connection.getMinimumBalanceForRentExemption(space),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        initializeExtension(mint.publicKey),
+        createInitializeMintInstruction(mint.publicKey, DECIMALS, authority.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(connection, tx, [authority, mint]);
+      return mint.publicKey;
+    };
+
+    it("Credits the net amount when the mint charges a transfer fee", async () => {
+      const authority = Keypair.generate();
+      await airdrop(program.provider.connection, authority.publicKey);
+      // 1% 转账手续费
+      const tokenMint = await createMint2022(authority, [ExtensionType.TransferFeeConfig], (mint) =>
+        createInitializeTransferFeeConfigInstruction(
+          mint, authority.publicKey, authority.publicKey, 100, BigInt(tokens(1000).toString()), TOKEN_2022_PROGRAM_ID
+        )
+      );
+      const dao = await createTestDao(program, {}, { authority, tokenMint, tokenProgram: TOKEN_2022_PROGRAM_ID });
+      const feeStaker = await createStaker(dao, tokens(1000));
+
+      const stakeAccount = await stake(dao, feeStaker, tokens(1000));
+
+      const netAmount = tokens(990);
+      assert.equal((await program.account.stakeAccount.fetch(stakeAccount)).amount.toString(), netAmount.toString());
+      assert.equal(
+        (await program.account.daoState.fetch(dao.daoState)).totalStakedAmount.toString(),
+        netAmount.toString()
+      );
+      const vault = await getAccount(program.provider.connection, dao.governanceVault, undefined, TOKEN_2022_PROGRAM_ID);
+      assert.equal(vault.amount.toString(), netAmount.toString());
+    });
+
+    it("Rejects a mint with a permanent delegate", async () => {
+      const authority = Keypair.generate();
+      await airdrop(program.provider.connection, authority.publicKey);
+      const tokenMint = await createMint2022(authority, [ExtensionType.PermanentDelegate], (mint) =>
+        createInitializePermanentDelegateInstruction(mint, authority.publicKey, TOKEN_2022_PROGRAM_ID)
+      );
+
+      await expectError(
+        createTestDao(program, {}, { authority, tokenMint, tokenProgram: TOKEN_2022_PROGRAM_ID }),
+        "UnsupportedMintExtension"
+      );
+    });
+  });
+
+  describe("Editing proposal metadata", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    const secondSigner = Keypair.generate();
+
+    const updateMetadata = (proposal: PublicKey, title: string, description: string) =>
+      program.methods
+        .updateProposalMetadata(title, description, "ipfs://updated", Array(32).fill(1))
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          proposalMetadata: proposalMetadataFor(proposal),
+          proposer: dao.authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([dao.authority])
+        .rpc();
+    const metadataSize = async (proposal: PublicKey) =>
+      (await program.provider.connection.getAccountInfo(proposalMetadataFor(proposal)))!.data.length;
+    // discriminator + proposal + 三个字符串 + content_hash
+    const expectedSize = (title: string, description: string) =>
+      8 + 32 + 4 + title.length + 4 + description.length + 4 + "ipfs://updated".length + 32;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Reallocates the metadata account as the text grows and shrinks", async () => {
+      const proposal = await propose(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)], { submit: false });
+
+      const longDescription = "d".repeat(500);
+      await updateMetadata(proposal, "A longer title", longDescription);
+      assert.equal(await metadataSize(proposal), expectedSize("A longer title", longDescription));
+      const metadata = await program.account.proposalMetadata.fetch(proposalMetadataFor(proposal));
+      assert.equal(metadata.description, longDescription);
+      assert.equal(metadata.metadataUri, "ipfs://updated");
+
+      await updateMetadata(proposal, "Short", "");
+      assert.equal(await metadataSize(proposal), expectedSize("Short", ""));
+      assert.equal((await program.account.proposalMetadata.fetch(proposalMetadataFor(proposal))).title, "Short");
+    });
+
+    it("Locks the metadata after the first approval", async () => {
+      // 先通过治理增加第二个签名者并把阈值提高到 2，使提案在第一次批准后仍等待审批
+      const upgrade = await proposeAndApprove(dao, [
+        updateDao({ addSigner: { newSigner: secondSigner.publicKey } }),
+        updateDao({ changeThreshold: { newThreshold: 2 } }),
+      ]);
+      await vote(dao, upgrade, voter, { yes: {} });
+      await waitForVoteEnd(dao, upgrade);
+      await execute(dao, upgrade);
+
+      const proposal = await propose(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      // 已提交但尚未批准时仍可修改
+      await updateMetadata(proposal, "Before approval", "");
+      await approve(dao, proposal);
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { pendingApproval: {} });
+      assert.equal(proposalAccount.approvals.length, 1);
+
+      await expectError(updateMetadata(proposal, "After approval", ""), "ProposalMetadataLocked");
+    });
+  });
 });
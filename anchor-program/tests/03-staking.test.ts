@@ -2,8 +2,39 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Dao } from "../target/types/dao.js";
 import { assert } from "chai";
-import { SystemProgram, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { getAccount } from "@solana/spl-token";
+import { Keypair, SystemProgram, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createAssociatedTokenAccount, getAccount, mintTo } from "@solana/spl-token";
+import {
+  TestDao,
+  TestStaker,
+  approve,
+  createStaker,
+  createTestDao,
+  delegationFor,
+  eventsOf,
+  execute,
+  expectError,
+  finalize,
+  proposalMetadataFor,
+  proposalTransactionFor,
+  propose,
+  proposeAndApprove,
+  sleep,
+  stake,
+  stakeAccountFor,
+  submit,
+  tokens,
+  unbondingAccountFor,
+  unstake,
+  updateDao,
+  vote,
+  voteRecordFor,
+  waitForSnapshot,
+  waitForVoteEnd,
+  waitUntil,
+  withdrawTreasury,
+  writable,
+} from "./helpers.js";
 
 describe("03 - Staking, Voting and Execution Tests", () => {
   let testContext: any;
@@ -39,36 +70,6 @@ describe("03 - Staking, Voting and Execution Tests", () => {
     recipient = testContext.recipient;
   });
 
-  it("Stakes tokens", async () => {
-    const stakeAmount = new anchor.BN(500 * 10 ** 6);
-    const [stakeAccount] = PublicKey.findProgramAddressSync(
-      [Buffer.from("stake_account"), daoState.toBuffer(), staker.publicKey.toBuffer()],
-      program.programId
-    );
-
-    await program.methods
-      .stake(stakeAmount)
-      .accounts({
-        staker: staker.publicKey,
-        daoState: daoState,
-        stakerTokenAccount: stakerTokenAccount,
-        governanceVault: governanceVault,
-        stakeAccount: stakeAccount,
-        systemProgram: SystemProgram.programId,
-        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-      })
-      .signers([staker])
-      .rpc();
-
-    const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccount);
-    assert.ok(stakeAccountData.amount.eq(stakeAmount));
-
-    const vaultAccount = await getAccount(program.provider.connection, governanceVault);
-    assert.equal(vaultAccount.amount.toString(), stakeAmount.toString());
-
-    (global as any).stakeAccount = stakeAccount;
-  });
-
   it("Votes on proposals", async () => {
     // 投票国库提款提案
     const [stakeAccount] = PublicKey.findProgramAddressSync(
@@ -86,6 +87,7 @@ describe("03 - Staking, Voting and Execution Tests", () => {
       .accounts({
         voter: staker.publicKey,
         stakeAccount: stakeAccount,
+        councilStakeAccount: null,
         daoState: daoState,
         proposal: currentProposal,
         voteRecord: voteRecord1,
@@ -105,6 +107,7 @@ describe("03 - Staking, Voting and Execution Tests", () => {
       .accounts({
         voter: staker.publicKey,
         stakeAccount: stakeAccount,
+        councilStakeAccount: null,
         daoState: daoState,
         proposal: recurringPaymentProposal,
         voteRecord: voteRecord2,
@@ -138,19 +141,21 @@ describe("03 - Staking, Voting and Execution Tests", () => {
     const recipientBalanceBefore = await program.provider.connection.getBalance(recipient.publicKey);
     
     await program.methods
-      .executeProposal()
+      .executeProposal(null)
       .accounts({
         daoState: daoState,
         proposal: currentProposal,
-        authority: admin.publicKey,
+        payer: admin.publicKey,
         treasury: treasury,
-        recipient: recipient.publicKey,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts([
+        { pubkey: recipient.publicKey, isSigner: false, isWritable: true },
+      ])
       .rpc();
 
     const proposalAccount1 = await program.account.proposal.fetch(currentProposal);
-    assert.isTrue(proposalAccount1.executed);
+    assert.deepEqual(proposalAccount1.state, { executed: {} });
 
     // 执行定期支付提案
     const [recurringPayment] = PublicKey.findProgramAddressSync(
@@ -159,20 +164,21 @@ describe("03 - Staking, Voting and Execution Tests", () => {
     );
 
     await program.methods
-      .executeProposal()
+      .executeProposal(null)
       .accounts({
         daoState: daoState,
         proposal: recurringPaymentProposal,
-        authority: admin.publicKey,
+        payer: admin.publicKey,
         treasury: treasury,
-        recipient: recipient.publicKey,
-        recurringPayment: recurringPayment,
         systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts([
+        { pubkey: recurringPayment, isSigner: false, isWritable: true },
+      ])
       .rpc();
 
     const proposalAccount2 = await program.account.proposal.fetch(recurringPaymentProposal);
-    assert.isTrue(proposalAccount2.executed);
+    assert.deepEqual(proposalAccount2.state, { executed: {} });
 
     console.log("Both proposals executed successfully");
     
@@ -219,20 +225,1263 @@ describe("03 - Staking, Voting and Execution Tests", () => {
       program.programId
     );
 
+    const [unbondingAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unbonding"), daoState.toBuffer(), staker.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccount);
+    const halfStake = stakeAccountData.amount.divn(2);
+
+    // 先部分赎回，质押账户保留剩余余额
     await program.methods
-      .unstake()
+      .unstake(halfStake, { community: {} })
+      .accounts({
+        staker: staker.publicKey,
+        daoState: daoState,
+        stakeAccount: stakeAccount,
+        unbondingAccount: unbondingAccount,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([staker])
+      .rpc();
+
+    const remaining = await program.account.stakeAccount.fetch(stakeAccount);
+    assert.ok(remaining.amount.eq(stakeAccountData.amount.sub(halfStake)));
+
+    // 再赎回剩余部分，余额归零后质押账户被关闭
+    await program.methods
+      .unstake(remaining.amount, { community: {} })
       .accounts({
         staker: staker.publicKey,
         daoState: daoState,
-        stakerTokenAccount: stakerTokenAccount,
-        governanceVault: governanceVault,
         stakeAccount: stakeAccount,
+        unbondingAccount: unbondingAccount,
         systemProgram: SystemProgram.programId,
+      })
+      .signers([staker])
+      .rpc();
+
+    const closedStakeAccount = await program.provider.connection.getAccountInfo(stakeAccount);
+    assert.isNull(closedStakeAccount);
+
+    // 解绑中的代币不计入总质押量，解绑期（测试中为 0）结束后提取
+    const unbonding = await program.account.unbondingAccount.fetch(unbondingAccount);
+    assert.ok(unbonding.amount.eq(stakeAccountData.amount));
+    const daoStateAccount = await program.account.daoState.fetch(daoState);
+    assert.equal(daoStateAccount.totalStakedAmount.toString(), "0");
+
+    await program.methods
+      .withdrawUnbonded({ community: {} })
+      .accounts({
+        staker: staker.publicKey,
+        daoState: daoState,
+        stakerTokenAccount: stakerTokenAccount,
+        tokenMint: tokenMint.publicKey,
+        governanceVault: governanceVault,
+        unbondingAccount: unbondingAccount,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
       })
       .signers([staker])
       .rpc();
 
+    assert.isNull(await program.provider.connection.getAccountInfo(unbondingAccount));
+
     console.log("Tokens unstaked successfully");
   });
+
+  describe("Delegated voting", () => {
+    let dao: TestDao;
+    let delegate: TestStaker;
+    let delegator: TestStaker;
+    let overrider: TestStaker;
+    let proposal: PublicKey;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      delegate = await createStaker(dao, tokens(100));
+      delegator = await createStaker(dao, tokens(200));
+      overrider = await createStaker(dao, tokens(50));
+      await stake(dao, delegate, tokens(100));
+      await stake(dao, delegator, tokens(200));
+      await stake(dao, overrider, tokens(50));
+      for (const staker of [delegator, overrider]) {
+        await program.methods
+          .delegate()
+          .accounts({
+            delegator: staker.publicKey,
+            daoState: dao.daoState,
+            delegate: delegate.publicKey,
+            delegation: delegationFor(dao, staker.publicKey),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([staker.keypair])
+          .rpc();
+      }
+      await waitForSnapshot();
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, delegate.publicKey)]);
+    });
+
+    it("Lets a delegator override by voting before the delegate", async () => {
+      await vote(dao, proposal, overrider, { no: {} });
+      await vote(dao, proposal, delegate, { yes: {} }, {
+        delegators: [delegator.publicKey, overrider.publicKey],
+      });
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.yesVotes.toString(), tokens(300).toString());
+      assert.equal(proposalAccount.noVotes.toString(), tokens(50).toString());
+      assert.equal(proposalAccount.voterCount, 3);
+
+      const delegateRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, delegate.publicKey));
+      assert.equal(delegateRecord.delegatorCount, 1);
+      const delegatorRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, delegator.publicKey));
+      assert.ok(delegatorRecord.delegate.equals(delegate.publicKey));
+      assert.deepEqual(delegatorRecord.choice, { yes: {} });
+      const overriderRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, overrider.publicKey));
+      assert.isNull(overriderRecord.delegate);
+      assert.deepEqual(overriderRecord.choice, { no: {} });
+    });
+
+    it("Rejects changes to a vote cast by the delegate", async () => {
+      await expectError(
+        program.methods
+          .changeVote({ no: {} })
+          .accounts({
+            voter: delegator.publicKey,
+            proposal,
+            voteRecord: voteRecordFor(dao, proposal, delegator.publicKey),
+          })
+          .signers([delegator.keypair])
+          .rpc(),
+        "VoteCastByDelegate"
+      );
+    });
+
+    it("Moves the delegator records when the delegate changes vote", async () => {
+      const changeVote = (delegatorRecords: PublicKey[]) =>
+        program.methods
+          .changeVote({ no: {} })
+          .accounts({
+            voter: delegate.publicKey,
+            proposal,
+            voteRecord: voteRecordFor(dao, proposal, delegate.publicKey),
+          })
+          .remainingAccounts(
+            delegatorRecords.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+          )
+          .signers([delegate.keypair])
+          .rpc();
+
+      await expectError(changeVote([]), "MissingDelegatorVoteRecords");
+      await changeVote([voteRecordFor(dao, proposal, delegator.publicKey)]);
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.yesVotes.toString(), "0");
+      assert.equal(proposalAccount.noVotes.toString(), tokens(350).toString());
+      const delegatorRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, delegator.publicKey));
+      assert.deepEqual(delegatorRecord.choice, { no: {} });
+    });
+
+    it("Refuses to relinquish a vote that carries delegated power", async () => {
+      await expectError(
+        program.methods
+          .relinquishVote()
+          .accounts({
+            voter: delegate.publicKey,
+            proposal,
+            voteRecord: voteRecordFor(dao, proposal, delegate.publicKey),
+          })
+          .signers([delegate.keypair])
+          .rpc(),
+        "CannotRelinquishDelegatedVote"
+      );
+    });
+  });
+
+  describe("Abstaining, changing and relinquishing votes", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    let proposal: PublicKey;
+    let voteRecord: PublicKey;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+    });
+
+    it("Counts an abstain vote outside the yes/no tally", async () => {
+      voteRecord = await vote(dao, proposal, voter, { abstain: {} });
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.abstainVotes.toString(), tokens(100).toString());
+      assert.equal(proposalAccount.yesVotes.toString(), "0");
+      assert.equal(proposalAccount.noVotes.toString(), "0");
+      assert.equal(proposalAccount.voterCount, 1);
+    });
+
+    it("Moves the weight when the vote is changed", async () => {
+      await program.methods
+        .changeVote({ yes: {} })
+        .accounts({ voter: voter.publicKey, proposal, voteRecord })
+        .signers([voter.keypair])
+        .rpc();
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.abstainVotes.toString(), "0");
+      assert.equal(proposalAccount.yesVotes.toString(), tokens(100).toString());
+      assert.equal(proposalAccount.voterCount, 1);
+      const record = await program.account.voteRecord.fetch(voteRecord);
+      assert.deepEqual(record.choice, { yes: {} });
+    });
+
+    it("Removes the vote and closes the record when relinquished", async () => {
+      await program.methods
+        .relinquishVote()
+        .accounts({ voter: voter.publicKey, proposal, voteRecord })
+        .signers([voter.keypair])
+        .rpc();
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.yesVotes.toString(), "0");
+      assert.equal(proposalAccount.voterCount, 0);
+      assert.equal(proposalAccount.rawVotes.toString(), "0");
+      assert.isNull(await program.provider.connection.getAccountInfo(voteRecord));
+
+      // 撤回后可以重新投票
+      await vote(dao, proposal, voter, { no: {} });
+      const revoted = await program.account.proposal.fetch(proposal);
+      assert.equal(revoted.noVotes.toString(), tokens(100).toString());
+    });
+  });
+
+  describe("Arbitrary instruction proposals", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    let proposal: PublicKey;
+    const payee = Keypair.generate();
+    const amount = 0.5 * LAMPORTS_PER_SOL;
+
+    const toProposalInstruction = (ix: anchor.web3.TransactionInstruction) => ({
+      programId: ix.programId,
+      accounts: ix.keys,
+      data: ix.data,
+    });
+
+    const addTransaction = (instructions: any[]) =>
+      program.methods
+        .addProposalTransaction(0, instructions)
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          proposalTransaction: proposalTransactionFor(dao, proposal, 0),
+          proposer: dao.authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([dao.authority])
+        .rpc();
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+      proposal = await propose(dao, [{ executeInstructions: { instructionCount: 1 } }], { submit: false });
+    });
+
+    it("Refuses to submit an instruction proposal before its instructions are attached", async () => {
+      await expectError(submit(dao, proposal, dao.authority), "MissingProposalTransaction");
+      await expectError(approve(dao, proposal), "InvalidProposalState");
+    });
+
+    it("Rejects stored instructions that require a signer other than the treasury", async () => {
+      const borrowed = SystemProgram.transfer({
+        fromPubkey: admin.publicKey,
+        toPubkey: payee.publicKey,
+        lamports: amount,
+      });
+      await expectError(addTransaction([toProposalInstruction(borrowed)]), "InvalidInstructionSigner");
+    });
+
+    it("Executes the stored instruction signed by the treasury", async () => {
+      const transfer = SystemProgram.transfer({
+        fromPubkey: dao.treasury,
+        toPubkey: payee.publicKey,
+        lamports: amount,
+      });
+      await addTransaction([toProposalInstruction(transfer)]);
+      await submit(dao, proposal, dao.authority, [proposalTransactionFor(dao, proposal, 0)]);
+      await approve(dao, proposal);
+      await vote(dao, proposal, voter, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+
+      // 不传 payer：执行上下文中没有可供指令借用的签名者
+      await execute(dao, proposal, [
+        { pubkey: proposalTransactionFor(dao, proposal, 0), isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        writable(dao.treasury),
+        writable(payee.publicKey),
+      ]);
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { executed: {} });
+      assert.equal(await program.provider.connection.getBalance(payee.publicKey), amount);
+    });
+  });
+
+  describe("Guardian veto", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    let proposal: PublicKey;
+    const guardian = Keypair.generate();
+
+    const veto = (signer: Keypair) =>
+      program.methods
+        .vetoProposal("Malicious withdrawal")
+        .accounts({ daoState: dao.daoState, proposal, guardian: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+
+      // 通过治理任命守护者并开启一小时的时间锁
+      const setup = await proposeAndApprove(dao, [
+        { updateDao: { action: { appointGuardian: { guardian: guardian.publicKey } } } },
+        {
+          updateDao: {
+            action: {
+              changeTimelock: {
+                holdUpTime: new anchor.BN(60 * 60),
+                executionWindow: new anchor.BN(7 * 24 * 60 * 60),
+              },
+            },
+          },
+        },
+      ]);
+      await vote(dao, setup, voter, { yes: {} });
+      await waitForVoteEnd(dao, setup);
+      await execute(dao, setup);
+
+      const daoStateAccount = await program.account.daoState.fetch(dao.daoState);
+      assert.ok(daoStateAccount.guardian.equals(guardian.publicKey));
+    });
+
+    it("Cannot veto while the vote is still open", async () => {
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      await vote(dao, proposal, voter, { yes: {} });
+      await expectError(veto(guardian), "VotePeriodNotOver");
+    });
+
+    it("Only the guardian can veto", async () => {
+      await waitForVoteEnd(dao, proposal);
+      await expectError(veto(dao.authority), "UnauthorizedGuardian");
+    });
+
+    it("Vetoes a passed proposal during the hold-up without a prior finalize", async () => {
+      // 投票已结束但尚未 finalize，否决时先计票
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { voting: {} });
+      await veto(guardian);
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { cancelled: {} });
+      assert.ok(proposalAccount.finalizedAt);
+      await expectError(
+        execute(dao, proposal, [writable(voter.publicKey)]),
+        "InvalidProposalState"
+      );
+    });
+  });
+
+  describe("Quorum modes", () => {
+    // 在新的 DAO 中为每组投票者各发起一个提案，全部投赞成票后返回计票结果
+    const tallyWith = async (
+      quorumMode: any,
+      quorum: anchor.BN,
+      stakeAmounts: number[],
+      voterGroups: number[][]
+    ) => {
+      const dao = await createTestDao(program, { quorumMode, quorum });
+      const stakers: TestStaker[] = [];
+      for (const amount of stakeAmounts) {
+        const staker = await createStaker(dao, tokens(amount));
+        await stake(dao, staker, tokens(amount));
+        stakers.push(staker);
+      }
+      await waitForSnapshot();
+
+      const proposals: PublicKey[] = [];
+      for (const voters of voterGroups) {
+        const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, stakers[0].publicKey)]);
+        for (const index of voters) {
+          await vote(dao, proposal, stakers[index], { yes: {} });
+        }
+        proposals.push(proposal);
+      }
+
+      const states = [];
+      for (const proposal of proposals) {
+        await waitForVoteEnd(dao, proposal);
+        await finalize(dao, proposal);
+        states.push((await program.account.proposal.fetch(proposal)).state);
+      }
+      return states;
+    };
+
+    it("MinVoters requires enough distinct voters", async () => {
+      const states = await tallyWith({ minVoters: {} }, new anchor.BN(2), [100, 100], [[0], [0, 1]]);
+      assert.deepEqual(states, [{ defeated: {} }, { succeeded: {} }]);
+    });
+
+    it("MinWeight requires enough voting weight", async () => {
+      const states = await tallyWith({ minWeight: {} }, tokens(150), [100, 100], [[0], [0, 1]]);
+      assert.deepEqual(states, [{ defeated: {} }, { succeeded: {} }]);
+    });
+
+    it("PercentOfTotalStaked compares against the snapshot total stake", async () => {
+      // 总质押 400：100 占 25%，300 占 75%
+      const states = await tallyWith({ percentOfTotalStaked: {} }, new anchor.BN(50), [100, 300], [[0], [1]]);
+      assert.deepEqual(states, [{ defeated: {} }, { succeeded: {} }]);
+    });
+  });
+
+  describe("Reclaiming rent", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    let proposal: PublicKey;
+    let voteRecord: PublicKey;
+
+    const closeVoteRecord = () =>
+      program.methods
+        .closeVoteRecord()
+        .accounts({ proposal, voteRecord, rentReceiver: voter.publicKey })
+        .rpc();
+
+    before(async () => {
+      dao = await createTestDao(program, { proposalRetentionPeriod: 0 });
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Closes a vote record once the proposal is finalized", async () => {
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      voteRecord = await vote(dao, proposal, voter, { no: {} });
+      await expectError(closeVoteRecord(), "ProposalNotFinalized");
+
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { defeated: {} });
+
+      const balanceBefore = await program.provider.connection.getBalance(voter.publicKey);
+      await closeVoteRecord();
+      assert.isNull(await program.provider.connection.getAccountInfo(voteRecord));
+      assert.isAbove(await program.provider.connection.getBalance(voter.publicKey), balanceBefore);
+    });
+
+    it("Keeps the vote record of a passed proposal until it is executed", async () => {
+      const passed = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      const record = await vote(dao, passed, voter, { yes: {} });
+      const closeRecord = () =>
+        program.methods
+          .closeVoteRecord()
+          .accounts({ proposal: passed, voteRecord: record, rentReceiver: voter.publicKey })
+          .rpc();
+
+      await waitForVoteEnd(dao, passed);
+      await finalize(dao, passed);
+      assert.deepEqual((await program.account.proposal.fetch(passed)).state, { succeeded: {} });
+      await expectError(closeRecord(), "ProposalNotFinalized");
+
+      await execute(dao, passed, [writable(voter.publicKey)]);
+      await closeRecord();
+      assert.isNull(await program.provider.connection.getAccountInfo(record));
+    });
+
+    it("Closes the finalized proposal after the retention period", async () => {
+      await program.methods
+        .closeProposal()
+        .accounts({
+          daoState: dao.daoState,
+          proposal,
+          proposalMetadata: proposalMetadataFor(dao, proposal),
+          proposer: dao.authority.publicKey,
+        })
+        .rpc();
+
+      assert.isNull(await program.provider.connection.getAccountInfo(proposal));
+      assert.isNull(await program.provider.connection.getAccountInfo(proposalMetadataFor(dao, proposal)));
+    });
+
+    it("Closes the instruction account of a cancelled proposal", async () => {
+      const instructionProposal = await propose(dao, [{ executeInstructions: { instructionCount: 1 } }], {
+        submit: false,
+      });
+      const proposalTransaction = proposalTransactionFor(dao, instructionProposal, 0);
+      const transfer = SystemProgram.transfer({
+        fromPubkey: dao.treasury,
+        toPubkey: voter.publicKey,
+        lamports: LAMPORTS_PER_SOL,
+      });
+      await program.methods
+        .addProposalTransaction(0, [{ programId: transfer.programId, accounts: transfer.keys, data: transfer.data }])
+        .accounts({
+          daoState: dao.daoState,
+          proposal: instructionProposal,
+          proposalTransaction,
+          proposer: dao.authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([dao.authority])
+        .rpc();
+
+      const closeTransaction = () =>
+        program.methods
+          .closeProposalTransaction()
+          .accounts({
+            proposal: instructionProposal,
+            proposalTransaction,
+            proposer: dao.authority.publicKey,
+          })
+          .rpc();
+      await expectError(closeTransaction(), "ProposalNotFinalized");
+
+      await program.methods
+        .cancelProposal()
+        .accounts({
+          daoState: dao.daoState,
+          proposal: instructionProposal,
+          proposalMetadata: proposalMetadataFor(dao, instructionProposal),
+          treasury: dao.treasury,
+          proposer: dao.authority.publicKey,
+        })
+        .signers([dao.authority])
+        .rpc();
+
+      // 提案账户已关闭，指令账户仍可关闭并返还租金
+      const balanceBefore = await program.provider.connection.getBalance(dao.authority.publicKey);
+      await closeTransaction();
+      assert.isNull(await program.provider.connection.getAccountInfo(proposalTransaction));
+      assert.isAbove(await program.provider.connection.getBalance(dao.authority.publicKey), balanceBefore);
+    });
+  });
+
+  describe("Staking rewards", () => {
+    let dao: TestDao;
+    let staker: TestStaker;
+    const funded = tokens(10);
+
+    before(async () => {
+      // 500% 年化，几秒内即可累计出可领取的奖励
+      dao = await createTestDao(program, { stakingYieldRate: 50000 });
+      staker = await createStaker(dao, tokens(1000));
+      await stake(dao, staker, tokens(1000));
+    });
+
+    it("Accrues rewards from the funded vault at staking_yield_rate", async () => {
+      const connection = program.provider.connection;
+      const funderTokenAccount = await createAssociatedTokenAccount(
+        connection, dao.authority, dao.tokenMint, dao.authority.publicKey
+      );
+      await mintTo(connection, dao.authority, dao.tokenMint, funderTokenAccount, dao.authority, BigInt(funded.toString()));
+      await program.methods
+        .fundRewards(funded)
+        .accounts({
+          funder: dao.authority.publicKey,
+          daoState: dao.daoState,
+          funderTokenAccount,
+          tokenMint: dao.tokenMint,
+          rewardVault: dao.rewardVault,
+          tokenProgram: dao.tokenProgram,
+        })
+        .signers([dao.authority])
+        .rpc();
+      assert.equal((await program.account.daoState.fetch(dao.daoState)).undistributedRewards.toString(), funded.toString());
+
+      await sleep(3000);
+      await program.methods
+        .claimRewards()
+        .accounts({
+          staker: staker.publicKey,
+          daoState: dao.daoState,
+          stakerTokenAccount: staker.tokenAccount,
+          tokenMint: dao.tokenMint,
+          rewardVault: dao.rewardVault,
+          stakeAccount: stakeAccountFor(dao, staker.publicKey),
+          tokenProgram: dao.tokenProgram,
+        })
+        .signers([staker.keypair])
+        .rpc();
+
+      const claimed = (await getAccount(connection, staker.tokenAccount)).amount;
+      assert.isTrue(claimed > 0n);
+      const daoStateAccount = await program.account.daoState.fetch(dao.daoState);
+      assert.isTrue(BigInt(daoStateAccount.undistributedRewards.toString()) + claimed <= BigInt(funded.toString()));
+      const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccountFor(dao, staker.publicKey));
+      assert.equal(stakeAccountData.pendingRewards.toString(), "0");
+    });
+
+    it("Funds the reward vault from the treasury through a proposal", async () => {
+      const connection = program.provider.connection;
+      // 国库 PDA 持有的治理代币账户
+      const treasuryTokenAccount = await createAssociatedTokenAccount(
+        connection, dao.authority, dao.tokenMint, dao.treasury, undefined, undefined, undefined, true
+      );
+      await mintTo(connection, dao.authority, dao.tokenMint, treasuryTokenAccount, dao.authority, BigInt(funded.toString()));
+      await waitForSnapshot();
+
+      const before = await program.account.daoState.fetch(dao.daoState);
+      const proposal = await proposeAndApprove(dao, [{ fundRewards: { amount: funded } }]);
+      await vote(dao, proposal, staker, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+      await execute(dao, proposal, [
+        writable(treasuryTokenAccount),
+        { pubkey: dao.tokenMint, isSigner: false, isWritable: false },
+        writable(dao.rewardVault),
+        { pubkey: dao.tokenProgram, isSigner: false, isWritable: false },
+      ]);
+
+      // 注资前的奖励按旧余额累计，注入的数量全部计入待分配奖励
+      const after = await program.account.daoState.fetch(dao.daoState);
+      assert.isTrue(after.undistributedRewards.gt(before.undistributedRewards));
+      assert.isTrue(after.undistributedRewards.lte(before.undistributedRewards.add(funded)));
+      assert.equal((await getAccount(connection, treasuryTokenAccount)).amount, 0n);
+    });
+  });
+
+  describe("Vote locks", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    let delegator: TestStaker;
+    let proposal: PublicKey;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      delegator = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await stake(dao, delegator, tokens(100));
+      await program.methods
+        .delegate()
+        .accounts({
+          delegator: delegator.publicKey,
+          daoState: dao.daoState,
+          delegate: voter.publicKey,
+          delegation: delegationFor(dao, delegator.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([delegator.keypair])
+        .rpc();
+      await waitForSnapshot();
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      await vote(dao, proposal, voter, { yes: {} }, { delegators: [delegator.publicKey] });
+    });
+
+    it("Locks the voter's and the delegator's stake until the vote ends", async () => {
+      const endTime = (await program.account.proposal.fetch(proposal)).endTime;
+      for (const staker of [voter, delegator]) {
+        const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccountFor(dao, staker.publicKey));
+        assert.equal(stakeAccountData.lockedUntil.toString(), endTime.toString());
+        await expectError(unstake(dao, staker, tokens(10)), "StakeLocked");
+      }
+    });
+
+    it("Releases the stake once the vote has ended", async () => {
+      await waitForVoteEnd(dao, proposal);
+      for (const staker of [voter, delegator]) {
+        await unstake(dao, staker, tokens(10));
+        const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccountFor(dao, staker.publicKey));
+        assert.equal(stakeAccountData.amount.toString(), tokens(90).toString());
+      }
+    });
+  });
+
+  describe("Vote-escrow lockups", () => {
+    let dao: TestDao;
+    let locked: TestStaker;
+    let unlocked: TestStaker;
+    let proposal: PublicKey;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      locked = await createStaker(dao, tokens(100));
+      unlocked = await createStaker(dao, tokens(100));
+      // 锁仓两年（最长四年）约为 2 倍权重
+      await stake(dao, locked, tokens(100), { lockDuration: new anchor.BN(2 * 365 * 24 * 60 * 60) });
+      await stake(dao, unlocked, tokens(100));
+      await waitForSnapshot();
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, locked.publicKey)]);
+    });
+
+    it("Multiplies voting weight by the remaining lock time", async () => {
+      await vote(dao, proposal, locked, { yes: {} });
+      await vote(dao, proposal, unlocked, { yes: {} });
+
+      const lockedRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, locked.publicKey));
+      const unlockedRecord = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, unlocked.publicKey));
+      assert.equal(unlockedRecord.weight.toString(), tokens(100).toString());
+      assert.equal(lockedRecord.rawStake.toString(), unlockedRecord.rawStake.toString());
+      // 倍数随剩余锁仓时间线性衰减，投票时略低于 2 倍
+      assert.isTrue(lockedRecord.weight.lte(tokens(200)));
+      assert.isTrue(lockedRecord.weight.gt(tokens(199)));
+    });
+
+    it("Keeps locked tokens from being unstaked", async () => {
+      await expectError(
+        program.methods
+          .unstake(tokens(10), { community: {} })
+          .accounts({
+            staker: locked.publicKey,
+            daoState: dao.daoState,
+            stakeAccount: stakeAccountFor(dao, locked.publicKey),
+            unbondingAccount: unbondingAccountFor(dao, locked.publicKey),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([locked.keypair])
+          .rpc(),
+        "StakeLocked"
+      );
+    });
+
+    it("Extends the lock only to a later end time", async () => {
+      const year = 365 * 24 * 60 * 60;
+      const extendLock = (duration: number) =>
+        program.methods
+          .extendLock(new anchor.BN(duration), { community: {} })
+          .accounts({
+            staker: locked.publicKey,
+            daoState: dao.daoState,
+            stakeAccount: stakeAccountFor(dao, locked.publicKey),
+          })
+          .signers([locked.keypair])
+          .rpc();
+      const before = await program.account.stakeAccount.fetch(stakeAccountFor(dao, locked.publicKey));
+
+      // 一年后到期早于当前两年的锁仓
+      await expectError(extendLock(year), "LockNotExtended");
+
+      await extendLock(3 * year);
+      const after = await program.account.stakeAccount.fetch(stakeAccountFor(dao, locked.publicKey));
+      assert.isTrue(after.lockEnd.gt(before.lockEnd));
+      // 按新的剩余锁仓时间重新计算倍数：10000 + 20000 * 3 / 4
+      assert.equal(after.lockMultiplier, 25000);
+      assert.equal(after.lockEnd.sub(after.lockStart).toNumber(), 3 * year);
+    });
+  });
+
+  describe("Quadratic voting", () => {
+    let dao: TestDao;
+    let whale: TestStaker;
+    let minnow: TestStaker;
+
+    before(async () => {
+      dao = await createTestDao(program, {
+        votingMode: { quadratic: {} },
+        quorumMode: { percentOfTotalStaked: {} },
+        quorum: new anchor.BN(50),
+        passThresholdPercentage: 70,
+      });
+      whale = await createStaker(dao, tokens(400));
+      minnow = await createStaker(dao, tokens(100));
+      await stake(dao, whale, tokens(400));
+      await stake(dao, minnow, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Weights votes by the square root of the stake and measures quorum on raw stake", async () => {
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, whale.publicKey)]);
+      await vote(dao, proposal, whale, { yes: {} });
+
+      const record = await program.account.voteRecord.fetch(voteRecordFor(dao, proposal, whale.publicKey));
+      // sqrt(400 * 10^6) = 20000
+      assert.equal(record.weight.toString(), "20000");
+      assert.equal(record.rawStake.toString(), tokens(400).toString());
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.rawVotes.toString(), tokens(400).toString());
+
+      // 400 / 500 的质押参与投票，达到 50% 的法定人数（换算后的权重远低于总质押量）
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { succeeded: {} });
+    });
+
+    it("Uses the applied weight for the pass ratio", async () => {
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, whale.publicKey)]);
+      await vote(dao, proposal, whale, { yes: {} });
+      await vote(dao, proposal, minnow, { no: {} });
+
+      // 按质押数量赞成占 80%，按平方根权重只占 20000 / 30000 ≈ 67%，低于 70%
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { defeated: {} });
+    });
+
+    it("Keeps the voting mode locked at approval when it changes mid-vote", async () => {
+      const modeDao = await createTestDao(program, { votingMode: { quadratic: {} }, voteDuration: 8 });
+      const early = await createStaker(modeDao, tokens(400));
+      const late = await createStaker(modeDao, tokens(100));
+      await stake(modeDao, early, tokens(400));
+      await stake(modeDao, late, tokens(100));
+      await waitForSnapshot();
+
+      const switchToLinear = await proposeAndApprove(modeDao, [updateDao({ changeVotingMode: { votingMode: { linear: {} } } })]);
+      await vote(modeDao, switchToLinear, early, { yes: {} });
+      // 晚于修改提案开始投票，修改执行时仍在投票中
+      await sleep(3000);
+      const proposal = await proposeAndApprove(modeDao, [withdrawTreasury(LAMPORTS_PER_SOL, early.publicKey)]);
+      await vote(modeDao, proposal, early, { yes: {} });
+
+      await waitForVoteEnd(modeDao, switchToLinear);
+      await execute(modeDao, switchToLinear);
+      assert.deepEqual((await program.account.daoState.fetch(modeDao.daoState)).votingMode, { linear: {} });
+
+      await vote(modeDao, proposal, late, { no: {} });
+      const earlyRecord = await program.account.voteRecord.fetch(voteRecordFor(modeDao, proposal, early.publicKey));
+      const lateRecord = await program.account.voteRecord.fetch(voteRecordFor(modeDao, proposal, late.publicKey));
+      // 两票都按批准时的平方根模式换算：sqrt(400 * 10^6) 与 sqrt(100 * 10^6)
+      assert.equal(earlyRecord.weight.toString(), "20000");
+      assert.equal(lateRecord.weight.toString(), "10000");
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).votingMode, { quadratic: {} });
+    });
+  });
+
+  describe("Multiple-choice proposals", () => {
+    let dao: TestDao;
+    let stakers: TestStaker[];
+    const payeeA = Keypair.generate();
+    const payeeB = Keypair.generate();
+
+    const option = (label: string, actionIndex: number | null) => ({
+      label,
+      actionIndex,
+      votes: new anchor.BN(0),
+      voterCount: 0,
+      rawVotes: new anchor.BN(0),
+    });
+    const createMultipleChoice = () =>
+      proposeAndApprove(
+        dao,
+        [withdrawTreasury(LAMPORTS_PER_SOL, payeeA.publicKey), withdrawTreasury(2 * LAMPORTS_PER_SOL, payeeB.publicKey)],
+        { options: [option("Pay A", 0), option("Pay B", 1), option("Pay nobody", null)] }
+      );
+
+    before(async () => {
+      dao = await createTestDao(program);
+      stakers = [];
+      for (const amount of [300, 100, 100, 100]) {
+        const staker = await createStaker(dao, tokens(amount));
+        await stake(dao, staker, tokens(amount));
+        stakers.push(staker);
+      }
+      await waitForSnapshot();
+    });
+
+    it("Executes only the winning option's action", async () => {
+      const proposal = await createMultipleChoice();
+      await expectError(vote(dao, proposal, stakers[0], { yes: {} }), "InvalidVoteChoice");
+      await vote(dao, proposal, stakers[0], { option: { index: 0 } });
+      await vote(dao, proposal, stakers[1], { option: { index: 1 } });
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.options[0].votes.toString(), tokens(300).toString());
+      assert.equal(proposalAccount.options[0].voterCount, 1);
+      assert.equal(proposalAccount.options[0].rawVotes.toString(), tokens(300).toString());
+      assert.equal(proposalAccount.options[1].votes.toString(), tokens(100).toString());
+
+      await waitForVoteEnd(dao, proposal);
+      await execute(dao, proposal, [writable(payeeA.publicKey)]);
+
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { executed: {} });
+      assert.equal(await program.provider.connection.getBalance(payeeA.publicKey), LAMPORTS_PER_SOL);
+      assert.equal(await program.provider.connection.getBalance(payeeB.publicKey), 0);
+    });
+
+    it("Defeats a proposal whose leading options tie", async () => {
+      const proposal = await createMultipleChoice();
+      await vote(dao, proposal, stakers[1], { option: { index: 0 } });
+      await vote(dao, proposal, stakers[2], { option: { index: 1 } });
+
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { defeated: {} });
+    });
+
+    it("Passes a plurality winner below the yes/no pass threshold", async () => {
+      const proposal = await createMultipleChoice();
+      await vote(dao, proposal, stakers[0], { option: { index: 0 } });
+      await vote(dao, proposal, stakers[1], { option: { index: 1 } });
+      await vote(dao, proposal, stakers[2], { option: { index: 1 } });
+      await vote(dao, proposal, stakers[3], { option: { index: 2 } });
+
+      // 300 / 600 = 50%，低于默认 60% 的通过比例，但仍是唯一的最高票
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { succeeded: {} });
+    });
+  });
+
+  describe("Council voting", () => {
+    let dao: TestDao;
+    let member: TestStaker;
+    let payee: Keypair;
+
+    before(async () => {
+      dao = await createTestDao(program, {}, { council: true });
+      payee = Keypair.generate();
+      member = await createStaker(dao, tokens(100), tokens(10));
+      await stake(dao, member, tokens(100));
+      await stake(dao, member, tokens(10), { council: true });
+      await waitForSnapshot();
+    });
+
+    it("Counts only council stake on a council proposal", async () => {
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)], {
+        population: { council: {} },
+      });
+      await vote(dao, proposal, member, { yes: {} }, { community: false, council: true });
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.yesVotes.toString(), tokens(10).toString());
+      assert.equal(proposalAccount.councilVotes.yesVotes.toNumber(), 0);
+      assert.equal(proposalAccount.snapshotTotalStaked.toString(), tokens(10).toString());
+
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { succeeded: {} });
+    });
+
+    it("Tallies community and council votes separately on a joint proposal", async () => {
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)], {
+        population: { both: {} },
+      });
+      await vote(dao, proposal, member, { yes: {} }, { community: true, council: true });
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.yesVotes.toString(), tokens(100).toString());
+      assert.equal(proposalAccount.councilVotes.yesVotes.toString(), tokens(10).toString());
+      assert.equal(proposalAccount.snapshotTotalStaked.toString(), tokens(100).toString());
+      assert.equal(proposalAccount.snapshotCouncilTotalStaked.toString(), tokens(10).toString());
+
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { succeeded: {} });
+    });
+  });
+
+  describe("Timelock and execution window", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    const payee = Keypair.generate();
+
+    // 投票通过后等待 3 秒才能执行，之后只有 3 秒的执行窗口
+    const passProposal = async () => {
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)]);
+      await vote(dao, proposal, voter, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+      await finalize(dao, proposal);
+      return proposal;
+    };
+
+    before(async () => {
+      dao = await createTestDao(program, { holdUpTime: 3, executionWindow: 3 });
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Cannot execute before the hold-up time has passed", async () => {
+      const proposal = await passProposal();
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { succeeded: {} });
+      assert.equal(proposalAccount.executableAt.toNumber(), proposalAccount.endTime.toNumber() + 3);
+      assert.equal(proposalAccount.executionDeadline.toNumber(), proposalAccount.executableAt.toNumber() + 3);
+
+      await expectError(execute(dao, proposal, [writable(payee.publicKey)]), "ProposalTimelocked");
+
+      await waitUntil(program.provider.connection, proposalAccount.executableAt.toNumber());
+      await execute(dao, proposal, [writable(payee.publicKey)]);
+      assert.deepEqual((await program.account.proposal.fetch(proposal)).state, { executed: {} });
+    });
+
+    it("Cannot execute after the execution deadline and finalizes to Expired", async () => {
+      const proposal = await passProposal();
+      const { executionDeadline } = await program.account.proposal.fetch(proposal);
+      // 执行窗口内不能标记为过期
+      await expectError(finalize(dao, proposal), "InvalidProposalState");
+
+      await waitUntil(program.provider.connection, executionDeadline.toNumber() + 1);
+      await expectError(execute(dao, proposal, [writable(payee.publicKey)]), "ProposalExpired");
+
+      await finalize(dao, proposal);
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.deepEqual(proposalAccount.state, { expired: {} });
+      assert.isNotNull(proposalAccount.finalizedAt);
+    });
+  });
+
+  describe("Governance rules", () => {
+    let dao: TestDao;
+    let whale: TestStaker;
+    let minnow: TestStaker;
+    const payee = Keypair.generate();
+    const withdrawRule = {
+      kind: { withdrawTreasury: {} },
+      voteDuration: new anchor.BN(8),
+      quorum: tokens(150),
+      passThresholdPercentage: 80,
+    };
+
+    // 修改 DAO 设置的提案使用默认规则，由 whale 单独投票通过
+    const passUpdate = async (action: any) => {
+      const proposal = await proposeAndApprove(dao, [updateDao(action)]);
+      await vote(dao, proposal, whale, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+      await execute(dao, proposal);
+    };
+    const votingDuration = async (proposal: PublicKey) => {
+      const { endTime, approvedAt } = await program.account.proposal.fetch(proposal);
+      return endTime.toNumber() - approvedAt.toNumber();
+    };
+
+    before(async () => {
+      dao = await createTestDao(program);
+      whale = await createStaker(dao, tokens(200));
+      minnow = await createStaker(dao, tokens(60));
+      await stake(dao, whale, tokens(200));
+      await stake(dao, minnow, tokens(60));
+      await waitForSnapshot();
+    });
+
+    it("Applies a per-kind rule's duration, quorum and threshold", async () => {
+      await passUpdate({ setGovernanceRule: { rule: withdrawRule } });
+      const daoStateAccount = await program.account.daoState.fetch(dao.daoState);
+      assert.equal(daoStateAccount.governanceRules.length, 1);
+      assert.equal(daoStateAccount.governanceRules[0].quorum.toString(), tokens(150).toString());
+
+      const belowThreshold = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)]);
+      const belowQuorum = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)]);
+      assert.equal(await votingDuration(belowThreshold), 8);
+      const proposalAccount = await program.account.proposal.fetch(belowThreshold);
+      assert.equal(proposalAccount.quorum.toString(), tokens(150).toString());
+      assert.equal(proposalAccount.passThresholdPercentage, 80);
+
+      // 200 / 260 ≈ 77%：达到法定人数，但低于规则的 80%（默认 60% 时会通过）
+      await vote(dao, belowThreshold, whale, { yes: {} });
+      await vote(dao, belowThreshold, minnow, { no: {} });
+      // 60 低于规则的法定人数 150（默认法定人数为 1）
+      await vote(dao, belowQuorum, minnow, { yes: {} });
+
+      await waitForVoteEnd(dao, belowQuorum);
+      await finalize(dao, belowThreshold);
+      await finalize(dao, belowQuorum);
+      assert.deepEqual((await program.account.proposal.fetch(belowThreshold)).state, { defeated: {} });
+      assert.deepEqual((await program.account.proposal.fetch(belowQuorum)).state, { defeated: {} });
+    });
+
+    it("Falls back to the DAO defaults once the rule is removed", async () => {
+      await passUpdate({ removeGovernanceRule: { kind: { withdrawTreasury: {} } } });
+      assert.equal((await program.account.daoState.fetch(dao.daoState)).governanceRules.length, 0);
+
+      const proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, payee.publicKey)]);
+      assert.equal(await votingDuration(proposal), 4);
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.quorum.toString(), "1");
+      assert.equal(proposalAccount.passThresholdPercentage, 60);
+
+      // 再次移除不存在的规则在创建提案时被拒绝
+      await expectError(
+        propose(dao, [updateDao({ removeGovernanceRule: { kind: { withdrawTreasury: {} } } })]),
+        "GovernanceRuleNotFound"
+      );
+    });
+  });
+
+  describe("DAO parameter updates", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Rejects out-of-range values when the proposal is created", async () => {
+      await expectError(
+        propose(dao, [updateDao({ changeQuorum: { quorumMode: { percentOfTotalStaked: {} }, quorum: new anchor.BN(101) } })]),
+        "InvalidQuorum"
+      );
+      await expectError(
+        propose(dao, [updateDao({ changeVoteDuration: { voteDuration: new anchor.BN(0) } })]),
+        "InvalidVoteDuration"
+      );
+    });
+
+    it("Applies quorum and vote duration changes and reports old and new values", async () => {
+      const proposal = await proposeAndApprove(dao, [
+        updateDao({ changeQuorum: { quorumMode: { minVoters: {} }, quorum: new anchor.BN(1) } }),
+        updateDao({ changeVoteDuration: { voteDuration: new anchor.BN(6) } }),
+      ]);
+      await vote(dao, proposal, voter, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+      const signature = await execute(dao, proposal);
+
+      const daoStateAccount = await program.account.daoState.fetch(dao.daoState);
+      assert.deepEqual(daoStateAccount.quorumMode, { minVoters: {} });
+      assert.equal(daoStateAccount.voteDuration.toNumber(), 6);
+
+      // 每个操作各触发一次参数修改事件
+      const updates = (await eventsOf(program, signature)).filter((event) => event.name === "daoParametersUpdated");
+      assert.equal(updates.length, 2);
+      const [quorumChange, durationChange] = updates.map((event) => event.data as any);
+      assert.deepEqual(quorumChange.oldParameters.quorumMode, { minWeight: {} });
+      assert.deepEqual(quorumChange.newParameters.quorumMode, { minVoters: {} });
+      assert.equal(quorumChange.newParameters.voteDuration.toNumber(), 4);
+      assert.equal(durationChange.oldParameters.voteDuration.toNumber(), 4);
+      assert.equal(durationChange.newParameters.voteDuration.toNumber(), 6);
+      assert.deepEqual(durationChange.oldParameters.quorumMode, { minVoters: {} });
+
+      // 之后批准的提案使用新的投票时长和法定人数计算方式
+      const next = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+      const nextAccount = await program.account.proposal.fetch(next);
+      assert.equal(nextAccount.endTime.toNumber() - nextAccount.approvedAt.toNumber(), 6);
+      assert.deepEqual(nextAccount.quorumMode, { minVoters: {} });
+    });
+  });
+
+  describe("Multi-action proposals", () => {
+    let dao: TestDao;
+    let voter: TestStaker;
+    const payeeA = Keypair.generate();
+    const payeeB = Keypair.generate();
+
+    before(async () => {
+      dao = await createTestDao(program);
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(100));
+      await waitForSnapshot();
+    });
+
+    it("Resumes execution one action at a time", async () => {
+      const connection = program.provider.connection;
+      const proposal = await proposeAndApprove(dao, [
+        withdrawTreasury(LAMPORTS_PER_SOL, payeeA.publicKey),
+        withdrawTreasury(2 * LAMPORTS_PER_SOL, payeeB.publicKey),
+      ]);
+      await vote(dao, proposal, voter, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+
+      // 第一次只执行第一个操作，提案保持 Succeeded
+      await execute(dao, proposal, [writable(payeeA.publicKey)], null, 1);
+      let proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.executedActions, 1);
+      assert.deepEqual(proposalAccount.state, { succeeded: {} });
+      assert.isNull(proposalAccount.finalizedAt);
+      assert.equal(await connection.getBalance(payeeA.publicKey), LAMPORTS_PER_SOL);
+      assert.equal(await connection.getBalance(payeeB.publicKey), 0);
+
+      // 继续执行时只需传入剩余操作的账户
+      await execute(dao, proposal, [writable(payeeB.publicKey)], null, 1);
+      proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.executedActions, 2);
+      assert.deepEqual(proposalAccount.state, { executed: {} });
+      assert.equal(await connection.getBalance(payeeB.publicKey), 2 * LAMPORTS_PER_SOL);
+
+      await expectError(execute(dao, proposal, [], null, 1), "ProposalAlreadyExecuted");
+    });
+
+    it("Runs every action in one transaction when no limit is given", async () => {
+      const payeeC = Keypair.generate();
+      const proposal = await proposeAndApprove(dao, [
+        withdrawTreasury(LAMPORTS_PER_SOL, payeeC.publicKey),
+        updateDao({ changeMinStakingAmount: { minStakingAmount: new anchor.BN(200) } }),
+      ]);
+      await vote(dao, proposal, voter, { yes: {} });
+      await waitForVoteEnd(dao, proposal);
+      await execute(dao, proposal, [writable(payeeC.publicKey)]);
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      assert.equal(proposalAccount.executedActions, 2);
+      assert.deepEqual(proposalAccount.state, { executed: {} });
+      assert.equal(await program.provider.connection.getBalance(payeeC.publicKey), LAMPORTS_PER_SOL);
+      assert.equal((await program.account.daoState.fetch(dao.daoState)).minStakingAmount.toNumber(), 200);
+    });
+  });
+
+  describe("Unbonding period", () => {
+    let dao: TestDao;
+    let staker: TestStaker;
+
+    const withdrawUnbonded = () =>
+      program.methods
+        .withdrawUnbonded({ community: {} })
+        .accounts({
+          staker: staker.publicKey,
+          daoState: dao.daoState,
+          stakerTokenAccount: staker.tokenAccount,
+          tokenMint: dao.tokenMint,
+          governanceVault: dao.governanceVault,
+          unbondingAccount: unbondingAccountFor(dao, staker.publicKey),
+          tokenProgram: dao.tokenProgram,
+        })
+        .signers([staker.keypair])
+        .rpc();
+
+    before(async () => {
+      dao = await createTestDao(program, { unbondingPeriod: 3 });
+      staker = await createStaker(dao, tokens(100));
+      await stake(dao, staker, tokens(100));
+    });
+
+    it("Holds unstaked tokens until the unbonding period has passed", async () => {
+      const connection = program.provider.connection;
+      await unstake(dao, staker, tokens(40));
+
+      // 解绑中的代币立即退出质押量，但还不能提取
+      assert.equal((await program.account.daoState.fetch(dao.daoState)).totalStakedAmount.toString(), tokens(60).toString());
+      const unbonding = await program.account.unbondingAccount.fetch(unbondingAccountFor(dao, staker.publicKey));
+      assert.equal(unbonding.amount.toString(), tokens(40).toString());
+      await expectError(withdrawUnbonded(), "UnbondingNotComplete");
+      assert.equal((await getAccount(connection, staker.tokenAccount)).amount, 0n);
+
+      await waitUntil(connection, unbonding.releaseAt.toNumber());
+      await withdrawUnbonded();
+      assert.equal((await getAccount(connection, staker.tokenAccount)).amount.toString(), tokens(40).toString());
+      assert.isNull(await connection.getAccountInfo(unbondingAccountFor(dao, staker.publicKey)));
+    });
+  });
+
+  describe("Stake history limit", () => {
+    const MAX_STAKE_CHECKPOINTS = 16;
+    let dao: TestDao;
+    let voter: TestStaker;
+    let proposal: PublicKey;
+
+    before(async () => {
+      dao = await createTestDao(program, { voteDuration: 60 });
+      voter = await createStaker(dao, tokens(100));
+      await stake(dao, voter, tokens(10));
+      await waitForSnapshot();
+      proposal = await proposeAndApprove(dao, [withdrawTreasury(LAMPORTS_PER_SOL, voter.publicKey)]);
+    });
+
+    it("Rejects a vote once the checkpoint at approval has been evicted", async () => {
+      // 每次质押落在不同的秒，各自占用一条快照，最终把批准前的快照挤出
+      for (let i = 0; i < MAX_STAKE_CHECKPOINTS; i++) {
+        await sleep(1100);
+        await stake(dao, voter, tokens(1));
+      }
+      const stakeAccountData = await program.account.stakeAccount.fetch(stakeAccountFor(dao, voter.publicKey));
+      assert.equal(stakeAccountData.checkpoints.length, MAX_STAKE_CHECKPOINTS);
+
+      await expectError(vote(dao, proposal, voter, { yes: {} }), "StakeHistoryTruncated");
+    });
+  });
 });
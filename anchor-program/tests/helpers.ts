@@ -0,0 +1,522 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dao } from "../target/types/dao.js";
+import { assert } from "chai";
+import {
+  AccountMeta,
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+} from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+
+// 行为测试各自创建独立的 DAO（DAO PDA 以 authority 为种子），互不影响，
+// 也不改动 01-init 创建的共享 DAO
+
+export const DECIMALS = 6;
+
+/// 按 6 位精度换算代币数量
+export const tokens = (amount: number): anchor.BN =>
+  new anchor.BN(amount).mul(new anchor.BN(10 ** DECIMALS));
+
+export const sleep = (ms: number) =>
+  new Promise((resolve) => setTimeout(resolve, ms));
+
+export async function airdrop(
+  connection: Connection,
+  to: PublicKey,
+  sol = 10
+): Promise<void> {
+  const signature = await connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  const latest = await connection.getLatestBlockhash();
+  await connection.confirmTransaction({ signature, ...latest }, "confirmed");
+}
+
+/// 等待链上时间达到 timestamp（投票结束、时间锁等按链上时钟判断）
+export async function waitUntil(
+  connection: Connection,
+  timestamp: number
+): Promise<void> {
+  for (;;) {
+    const now = await connection.getBlockTime(await connection.getSlot());
+    if (now !== null && now >= timestamp) {
+      return;
+    }
+    await sleep(500);
+  }
+}
+
+/// 断言交易因指定的程序错误失败
+export async function expectError(
+  promise: Promise<unknown>,
+  code: string
+): Promise<void> {
+  try {
+    await promise;
+  } catch (err) {
+    assert.equal((err as any).error?.errorCode?.code, code, String(err));
+    return;
+  }
+  assert.fail(`Expected the transaction to fail with ${code}`);
+}
+
+export interface DaoParams {
+  threshold: number;
+  voteDuration: number;
+  quorumMode: any;
+  quorum: anchor.BN;
+  stakingYieldRate: number;
+  passThresholdPercentage: number;
+  minStakingAmount: anchor.BN;
+  votingMode: any;
+  holdUpTime: number;
+  executionWindow: number;
+  proposalDeposit: anchor.BN;
+  proposalRetentionPeriod: number;
+  unbondingPeriod: number;
+  councilVotingMode: any;
+  councilQuorum: anchor.BN;
+}
+
+// 投票期留出几秒，保证批准后发送的投票交易仍在投票期内
+export const defaultDaoParams: DaoParams = {
+  threshold: 1,
+  voteDuration: 4,
+  quorumMode: { minWeight: {} },
+  quorum: new anchor.BN(1),
+  stakingYieldRate: 500,
+  passThresholdPercentage: 60,
+  minStakingAmount: new anchor.BN(100),
+  votingMode: { linear: {} },
+  holdUpTime: 0,
+  executionWindow: 7 * 24 * 60 * 60,
+  proposalDeposit: new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+  proposalRetentionPeriod: 30 * 24 * 60 * 60,
+  unbondingPeriod: 0,
+  councilVotingMode: { linear: {} },
+  councilQuorum: new anchor.BN(1),
+};
+
+export interface TestDao {
+  program: Program<Dao>;
+  authority: Keypair;
+  daoState: PublicKey;
+  treasury: PublicKey;
+  governanceVault: PublicKey;
+  rewardVault: PublicKey;
+  tokenMint: PublicKey;
+  tokenProgram: PublicKey;
+  councilMint: PublicKey | null;
+  councilVault: PublicKey | null;
+}
+
+export interface DaoOptions {
+  /// DAO 的 authority，同时作为测试代币的 mint authority
+  authority?: Keypair;
+  /// 预先创建的治理代币（如带扩展的 Token-2022 代币），mint authority 须为 authority
+  tokenMint?: PublicKey;
+  tokenProgram?: PublicKey;
+  /// 是否同时配置理事会代币
+  council?: boolean;
+}
+
+const pda = (program: Program<Dao>, seeds: Buffer[]): PublicKey =>
+  PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+export async function createTestDao(
+  program: Program<Dao>,
+  overrides: Partial<DaoParams> = {},
+  options: DaoOptions = {}
+): Promise<TestDao> {
+  const connection = program.provider.connection;
+  const params = { ...defaultDaoParams, ...overrides };
+  const authority = options.authority ?? Keypair.generate();
+  const tokenProgram = options.tokenProgram ?? TOKEN_PROGRAM_ID;
+  if (!options.authority) {
+    await airdrop(connection, authority.publicKey);
+  }
+
+  const tokenMint =
+    options.tokenMint ??
+    (await createMint(connection, authority, authority.publicKey, null, DECIMALS, undefined, undefined, tokenProgram));
+  const councilMint = options.council
+    ? await createMint(connection, authority, authority.publicKey, null, DECIMALS, undefined, undefined, tokenProgram)
+    : null;
+
+  const daoState = pda(program, [Buffer.from("dao"), authority.publicKey.toBuffer()]);
+  const dao: TestDao = {
+    program,
+    authority,
+    daoState,
+    treasury: pda(program, [Buffer.from("treasury"), daoState.toBuffer()]),
+    governanceVault: pda(program, [Buffer.from("governance_vault"), daoState.toBuffer()]),
+    rewardVault: pda(program, [Buffer.from("reward_vault"), daoState.toBuffer()]),
+    tokenMint,
+    tokenProgram,
+    councilMint,
+    councilVault: councilMint
+      ? pda(program, [Buffer.from("council_vault"), daoState.toBuffer()])
+      : null,
+  };
+
+  await program.methods
+    .initializeDao(
+      params.threshold,
+      new anchor.BN(params.voteDuration),
+      params.quorumMode,
+      params.quorum,
+      params.stakingYieldRate,
+      params.passThresholdPercentage,
+      params.minStakingAmount,
+      params.votingMode,
+      new anchor.BN(params.holdUpTime),
+      new anchor.BN(params.executionWindow),
+      params.proposalDeposit,
+      new anchor.BN(params.proposalRetentionPeriod),
+      new anchor.BN(params.unbondingPeriod),
+      params.councilVotingMode,
+      params.councilQuorum
+    )
+    .accounts({
+      daoState: dao.daoState,
+      authority: authority.publicKey,
+      treasury: dao.treasury,
+      tokenMint: dao.tokenMint,
+      governanceVault: dao.governanceVault,
+      rewardVault: dao.rewardVault,
+      councilMint: dao.councilMint,
+      councilVault: dao.councilVault,
+      config: pda(program, [Buffer.from("config")]),
+      systemProgram: SystemProgram.programId,
+      tokenProgram,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .signers([authority])
+    .rpc();
+
+  // 国库需要余额才能执行提款类提案
+  await airdrop(connection, dao.treasury, 5);
+  return dao;
+}
+
+// --- PDA ---
+
+export const stakeAccountFor = (dao: TestDao, staker: PublicKey, council = false) =>
+  pda(dao.program, [
+    Buffer.from(council ? "council_stake_account" : "stake_account"),
+    dao.daoState.toBuffer(),
+    staker.toBuffer(),
+  ]);
+
+export const unbondingAccountFor = (dao: TestDao, staker: PublicKey) =>
+  pda(dao.program, [Buffer.from("unbonding"), dao.daoState.toBuffer(), staker.toBuffer()]);
+
+export const delegationFor = (dao: TestDao, delegator: PublicKey) =>
+  pda(dao.program, [Buffer.from("delegation"), dao.daoState.toBuffer(), delegator.toBuffer()]);
+
+export const voteRecordFor = (dao: TestDao, proposal: PublicKey, voter: PublicKey) =>
+  pda(dao.program, [Buffer.from("vote_record"), proposal.toBuffer(), voter.toBuffer()]);
+
+export const proposalMetadataFor = (dao: TestDao, proposal: PublicKey) =>
+  pda(dao.program, [Buffer.from("proposal_metadata"), proposal.toBuffer()]);
+
+export const proposalTransactionFor = (dao: TestDao, proposal: PublicKey, actionIndex: number) =>
+  pda(dao.program, [Buffer.from("proposal_transaction"), proposal.toBuffer(), Buffer.from([actionIndex])]);
+
+async function nextProposal(dao: TestDao): Promise<PublicKey> {
+  const daoStateAccount = await dao.program.account.daoState.fetch(dao.daoState);
+  return pda(dao.program, [
+    Buffer.from("proposal"),
+    dao.daoState.toBuffer(),
+    daoStateAccount.proposalCount.toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+// --- 质押 ---
+
+export interface TestStaker {
+  keypair: Keypair;
+  publicKey: PublicKey;
+  tokenAccount: PublicKey;
+  councilTokenAccount: PublicKey | null;
+}
+
+/// 创建一个持有社区代币（以及可选的理事会代币）的钱包
+export async function createStaker(
+  dao: TestDao,
+  amount: anchor.BN,
+  councilAmount?: anchor.BN
+): Promise<TestStaker> {
+  const connection = dao.program.provider.connection;
+  const keypair = Keypair.generate();
+  await airdrop(connection, keypair.publicKey);
+
+  const tokenAccount = await createAssociatedTokenAccount(
+    connection, keypair, dao.tokenMint, keypair.publicKey, undefined, dao.tokenProgram
+  );
+  await mintTo(
+    connection, keypair, dao.tokenMint, tokenAccount, dao.authority, BigInt(amount.toString()), [], undefined, dao.tokenProgram
+  );
+
+  let councilTokenAccount: PublicKey | null = null;
+  if (councilAmount && dao.councilMint) {
+    councilTokenAccount = await createAssociatedTokenAccount(
+      connection, keypair, dao.councilMint, keypair.publicKey, undefined, dao.tokenProgram
+    );
+    await mintTo(
+      connection, keypair, dao.councilMint, councilTokenAccount, dao.authority, BigInt(councilAmount.toString()), [], undefined, dao.tokenProgram
+    );
+  }
+  return { keypair, publicKey: keypair.publicKey, tokenAccount, councilTokenAccount };
+}
+
+export async function stake(
+  dao: TestDao,
+  staker: TestStaker,
+  amount: anchor.BN,
+  { lockDuration = null as anchor.BN | null, council = false } = {}
+): Promise<PublicKey> {
+  const stakeAccount = stakeAccountFor(dao, staker.publicKey, council);
+  await dao.program.methods
+    .stake(amount, lockDuration, council ? { council: {} } : { community: {} })
+    .accounts({
+      staker: staker.publicKey,
+      daoState: dao.daoState,
+      stakerTokenAccount: council ? staker.councilTokenAccount : staker.tokenAccount,
+      tokenMint: council ? dao.councilMint : dao.tokenMint,
+      governanceVault: council ? dao.councilVault : dao.governanceVault,
+      stakeAccount,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: dao.tokenProgram,
+    })
+    .signers([staker.keypair])
+    .rpc();
+  return stakeAccount;
+}
+
+/// 赎回社区代币质押，代币进入解绑期
+export async function unstake(dao: TestDao, staker: TestStaker, amount: anchor.BN): Promise<void> {
+  await dao.program.methods
+    .unstake(amount, { community: {} })
+    .accounts({
+      staker: staker.publicKey,
+      daoState: dao.daoState,
+      stakeAccount: stakeAccountFor(dao, staker.publicKey),
+      unbondingAccount: unbondingAccountFor(dao, staker.publicKey),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([staker.keypair])
+    .rpc();
+}
+
+/// 投票权重取批准时刻之前的质押快照，质押后等待进入下一秒再批准提案
+export const waitForSnapshot = () => sleep(2000);
+
+// --- 提案 ---
+
+export interface ProposalOptions {
+  options?: any[];
+  population?: any;
+  /// 是否在创建后立即提交审批；需要先写入指令的提案传 false，之后调用 submit
+  submit?: boolean;
+}
+
+const metadataUri = "ipfs://test";
+const contentHash = Array(32).fill(0);
+
+/// 由 DAO 的 authority（唯一的多签签名者）发起多签提案
+export async function propose(
+  dao: TestDao,
+  actions: any[],
+  { options = [], population = { community: {} }, submit: submitNow = true }: ProposalOptions = {}
+): Promise<PublicKey> {
+  const proposal = await nextProposal(dao);
+  await dao.program.methods
+    .mulCreatePropose(actions, options, population, "Test proposal", "", metadataUri, contentHash)
+    .accounts({
+      daoState: dao.daoState,
+      proposal,
+      proposalMetadata: proposalMetadataFor(dao, proposal),
+      proposer: dao.authority.publicKey,
+      authority: dao.authority.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([dao.authority])
+    .rpc();
+  if (submitNow) {
+    await submit(dao, proposal, dao.authority);
+  }
+  return proposal;
+}
+
+/// 质押者缴纳押金发起提案
+export async function proposeAsStaker(
+  dao: TestDao,
+  staker: TestStaker,
+  actions: any[],
+  { options = [], population = { community: {} }, submit: submitNow = true }: ProposalOptions = {}
+): Promise<PublicKey> {
+  const proposal = await nextProposal(dao);
+  await dao.program.methods
+    .createStakerProposal(actions, options, population, "Staker proposal", "", metadataUri, contentHash)
+    .accounts({
+      daoState: dao.daoState,
+      proposal,
+      proposalMetadata: proposalMetadataFor(dao, proposal),
+      stakeAccount: stakeAccountFor(dao, staker.publicKey),
+      proposer: staker.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([staker.keypair])
+    .rpc();
+  if (submitNow) {
+    await submit(dao, proposal, staker.keypair);
+  }
+  return proposal;
+}
+
+/// 提交草稿进入审批，transactions 为各任意指令操作的待执行指令账户
+export async function submit(
+  dao: TestDao,
+  proposal: PublicKey,
+  proposer: Keypair,
+  transactions: PublicKey[] = []
+): Promise<void> {
+  await dao.program.methods
+    .submitProposal()
+    .accounts({ daoState: dao.daoState, proposal, proposer: proposer.publicKey })
+    .remainingAccounts(transactions.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+    .signers([proposer])
+    .rpc();
+}
+
+export async function approve(dao: TestDao, proposal: PublicKey): Promise<void> {
+  await dao.program.methods
+    .mulApprovePropose()
+    .accounts({
+      daoState: dao.daoState,
+      proposal,
+      approver: dao.authority.publicKey,
+      authority: dao.authority.publicKey,
+    })
+    .signers([dao.authority])
+    .rpc();
+}
+
+export async function proposeAndApprove(
+  dao: TestDao,
+  actions: any[],
+  proposalOptions: ProposalOptions = {}
+): Promise<PublicKey> {
+  const proposal = await propose(dao, actions, proposalOptions);
+  await approve(dao, proposal);
+  return proposal;
+}
+
+export interface VoteOptions {
+  /// 是否传入社区代币质押账户（仅代投的受托人可以不传）
+  community?: boolean;
+  /// 是否传入理事会代币质押账户
+  council?: boolean;
+  /// 委托给投票者的 (委托账户, 委托人质押账户, 委托人投票记录) 三元组
+  delegators?: PublicKey[];
+}
+
+export async function vote(
+  dao: TestDao,
+  proposal: PublicKey,
+  voter: TestStaker,
+  choice: any,
+  { community = true, council = false, delegators = [] }: VoteOptions = {}
+): Promise<PublicKey> {
+  const voteRecord = voteRecordFor(dao, proposal, voter.publicKey);
+  const remainingAccounts: AccountMeta[] = delegators.flatMap((delegator) => [
+    { pubkey: delegationFor(dao, delegator), isSigner: false, isWritable: false },
+    { pubkey: stakeAccountFor(dao, delegator), isSigner: false, isWritable: true },
+    { pubkey: voteRecordFor(dao, proposal, delegator), isSigner: false, isWritable: true },
+  ]);
+  await dao.program.methods
+    .vote(choice)
+    .accounts({
+      voter: voter.publicKey,
+      stakeAccount: community ? stakeAccountFor(dao, voter.publicKey) : null,
+      councilStakeAccount: council ? stakeAccountFor(dao, voter.publicKey, true) : null,
+      daoState: dao.daoState,
+      proposal,
+      voteRecord,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(remainingAccounts)
+    .signers([voter.keypair])
+    .rpc();
+  return voteRecord;
+}
+
+export async function waitForVoteEnd(dao: TestDao, proposal: PublicKey): Promise<void> {
+  const proposalAccount = await dao.program.account.proposal.fetch(proposal);
+  await waitUntil(dao.program.provider.connection, proposalAccount.endTime.toNumber());
+}
+
+export async function finalize(dao: TestDao, proposal: PublicKey): Promise<void> {
+  await dao.program.methods
+    .finalizeProposal()
+    .accounts({ daoState: dao.daoState, proposal })
+    .rpc();
+}
+
+/// 执行提案，返回交易签名。只有需要新建账户（定期支付）时才传入 payer；
+/// maxActions 为空时执行全部剩余操作
+export async function execute(
+  dao: TestDao,
+  proposal: PublicKey,
+  remainingAccounts: AccountMeta[] = [],
+  payer: PublicKey | null = null,
+  maxActions: number | null = null
+): Promise<string> {
+  return dao.program.methods
+    .executeProposal(maxActions)
+    .accounts({
+      daoState: dao.daoState,
+      proposal,
+      payer,
+      treasury: dao.treasury,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(remainingAccounts)
+    .rpc();
+}
+
+/// 解析交易日志中本程序触发的事件
+export async function eventsOf(program: Program<Dao>, signature: string) {
+  const connection = program.provider.connection;
+  for (;;) {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (tx) {
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      return [...parser.parseLogs(tx.meta?.logMessages ?? [])];
+    }
+    await sleep(500);
+  }
+}
+
+export const withdrawTreasury = (amount: number, recipient: PublicKey) => ({
+  withdrawTreasury: { amount: new anchor.BN(amount), recipient },
+});
+
+export const updateDao = (action: any) => ({ updateDao: { action } });
+
+export const writable = (pubkey: PublicKey): AccountMeta => ({
+  pubkey,
+  isSigner: false,
+  isWritable: true,
+});